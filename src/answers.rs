@@ -0,0 +1,148 @@
+use std::{collections::HashMap, path::Path};
+
+use derive_more::Display;
+
+use crate::error::{AocError, AocResult, ParseError};
+use crate::solver::Answer;
+
+/// Known correct answers per day and task, used to detect regressions.
+///
+/// The file format is one answer per line as "<day> <task> <answer>".
+/// Empty lines and lines starting with '#' are ignored.
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers {
+    answers: HashMap<(usize, usize), String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Display)]
+pub enum Verdict {
+    #[display(fmt = "PASS")]
+    Pass { expected: String },
+    #[display(fmt = "FAIL")]
+    Fail { expected: String },
+    #[display(fmt = "UNKNOWN")]
+    Unknown,
+}
+
+impl Verdict {
+    pub fn expected(&self) -> Option<&str> {
+        match self {
+            Verdict::Pass { expected } | Verdict::Fail { expected } => Some(expected),
+            Verdict::Unknown => None,
+        }
+    }
+}
+
+impl ExpectedAnswers {
+    pub fn from_file(path: &Path) -> AocResult<Self> {
+        let content =
+            std::fs::read_to_string(path).map_err(|error| AocError::reading_file(path, error))?;
+        Ok(Self::parse(&content)?)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut answers = HashMap::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line_number = index + 1;
+            let mut splitted = line.splitn(3, char::is_whitespace);
+            let (day, task, answer) = match (splitted.next(), splitted.next(), splitted.next()) {
+                (Some(day), Some(task), Some(answer)) => (day, task, answer.trim()),
+                _ => {
+                    return Err(
+                        ParseError::new("Expected \"<day> <task> <answer>\"").at_line(line_number)
+                    )
+                }
+            };
+            let (day, task) = match (day.parse(), task.parse()) {
+                (Ok(day), Ok(task)) => (day, task),
+                _ => {
+                    return Err(ParseError::new("Day and task must be numbers").at_line(line_number))
+                }
+            };
+            if answers.insert((day, task), answer.to_string()).is_some() {
+                return Err(ParseError::new(format!(
+                    "Second answer for task {} under day {}",
+                    task, day
+                ))
+                .at_line(line_number));
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn expected(&self, day: usize, task: usize) -> Option<&str> {
+        self.answers.get(&(day, task)).map(String::as_str)
+    }
+
+    pub fn verify(&self, day: usize, task: usize, actual: &Answer) -> Verdict {
+        match self.expected(day, task) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == actual.to_string() => Verdict::Pass {
+                expected: expected.to_string(),
+            },
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl FromIterator<((usize, usize), String)> for ExpectedAnswers {
+    fn from_iter<T: IntoIterator<Item = ((usize, usize), String)>>(iter: T) -> Self {
+        Self {
+            answers: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn parse_and_verify_answers() {
+        let answers = ExpectedAnswers::parse(
+            "# day task answer
+1 1 142
+
+1 2 281
+3 1 some text answer
+",
+        )
+        .unwrap();
+        assert_eq!(
+            Verdict::Pass {
+                expected: "142".to_string()
+            },
+            answers.verify(1, 1, &Answer::Unsigned(142))
+        );
+        assert_eq!(
+            Verdict::Fail {
+                expected: "281".to_string()
+            },
+            answers.verify(1, 2, &Answer::Unsigned(280))
+        );
+        assert_eq!(
+            Verdict::Pass {
+                expected: "some text answer".to_string()
+            },
+            answers.verify(3, 1, &Answer::Text("some text answer".to_string()))
+        );
+        assert_eq!(Verdict::Unknown, answers.verify(2, 1, &Answer::Unsigned(8)));
+    }
+
+    #[test]
+    fn reject_invalid_answers() {
+        assert_case("1 1");
+        assert_case("a 1 2");
+        assert_case("1 1 2\n1 1 3");
+        fn assert_case(input: &str) {
+            let actual = ExpectedAnswers::parse(input);
+            assert!(actual.is_err(), "Input: {}", input);
+        }
+    }
+}
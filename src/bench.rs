@@ -0,0 +1,313 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::BTreeMap,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use crate::error::{AocError, AocResult, ParseError};
+use crate::solver::Handler;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts every allocation before handing it to the system allocator.
+/// Only has an effect if installed as the `#[global_allocator]` by the binary.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+pub fn allocations_so_far() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spread {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Spread {
+    /// # Panics
+    ///
+    /// If no durations are given.
+    pub fn of(durations: &[Duration]) -> Self {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        Self {
+            min: *sorted.first().unwrap(),
+            median,
+            max: *sorted.last().unwrap(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    pub day: usize,
+    pub task: usize,
+    pub iterations: usize,
+    pub parse: Spread,
+    pub solve: Spread,
+    pub total: Spread,
+    pub mean_allocations: f64,
+}
+
+/// Runs the handler `iterations` times. At least one iteration is always done.
+pub fn bench(
+    handler: &Handler,
+    input: &str,
+    (day, task): (usize, usize),
+    iterations: usize,
+) -> AocResult<BenchReport> {
+    let iterations = iterations.max(1);
+    let (mut parse, mut solve, mut total) = (Vec::new(), Vec::new(), Vec::new());
+    let mut allocations = 0;
+    for _ in 0..iterations {
+        let allocations_before = allocations_so_far();
+        let solved = handler(input)?;
+        allocations += allocations_so_far() - allocations_before;
+        parse.push(solved.timings.parse);
+        solve.push(solved.timings.solve);
+        total.push(solved.timings.total());
+    }
+    Ok(BenchReport {
+        day,
+        task,
+        iterations,
+        parse: Spread::of(&parse),
+        solve: Spread::of(&solve),
+        total: Spread::of(&total),
+        mean_allocations: allocations as f64 / iterations as f64,
+    })
+}
+
+impl BenchReport {
+    pub fn render(&self) -> String {
+        let mut output = format!(
+            "Day {} part {}, {} iterations\n",
+            self.day, self.task, self.iterations
+        );
+        output.push_str(&format!(
+            "{:<6}{:>14}{:>14}{:>14}\n",
+            "Stage", "Min", "Median", "Max"
+        ));
+        for (name, spread) in [
+            ("Parse", &self.parse),
+            ("Solve", &self.solve),
+            ("Total", &self.total),
+        ] {
+            output.push_str(&format!(
+                "{:<6}{:>14}{:>14}{:>14}\n",
+                name,
+                format!("{:?}", spread.min),
+                format!("{:?}", spread.median),
+                format!("{:?}", spread.max)
+            ));
+        }
+        output.push_str(&format!("Mean allocations: {:.1}\n", self.mean_allocations));
+        output
+    }
+}
+
+/// Medians of an earlier bench run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BaselineEntry {
+    pub parse: Duration,
+    pub solve: Duration,
+    pub mean_allocations: f64,
+}
+
+impl BaselineEntry {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Stored bench results per day and task to compare later runs against.
+///
+/// The file format is one entry per line as
+/// "<day> <task> <median parse nanos> <median solve nanos> <mean allocations>".
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<(usize, usize), BaselineEntry>,
+}
+
+impl Baseline {
+    /// A not existing file is treated as an empty baseline.
+    pub fn from_file(path: &Path) -> AocResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            std::fs::read_to_string(path).map_err(|error| AocError::reading_file(path, error))?;
+        Ok(Self::parse(&content)?)
+    }
+
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        std::fs::write(path, self.render()).map_err(|error| {
+            AocError::io(format!("Could not write file {}", path.display()), error)
+        })
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut entries = BTreeMap::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            let invalid_line = || {
+                ParseError::new(
+                    "Expected \"<day> <task> <parse nanos> <solve nanos> <mean allocations>\"",
+                )
+                .at_line(index + 1)
+            };
+            let [day, task, parse, solve, mean_allocations] = columns.as_slice() else {
+                return Err(invalid_line());
+            };
+            let key = (
+                day.parse().map_err(|_| invalid_line())?,
+                task.parse().map_err(|_| invalid_line())?,
+            );
+            let entry = BaselineEntry {
+                parse: Duration::from_nanos(parse.parse().map_err(|_| invalid_line())?),
+                solve: Duration::from_nanos(solve.parse().map_err(|_| invalid_line())?),
+                mean_allocations: mean_allocations.parse().map_err(|_| invalid_line())?,
+            };
+            entries.insert(key, entry);
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn render(&self) -> String {
+        self.entries
+            .iter()
+            .map(|((day, task), entry)| {
+                format!(
+                    "{} {} {} {} {}\n",
+                    day,
+                    task,
+                    entry.parse.as_nanos(),
+                    entry.solve.as_nanos(),
+                    entry.mean_allocations
+                )
+            })
+            .collect()
+    }
+
+    pub fn get(&self, day: usize, task: usize) -> Option<&BaselineEntry> {
+        self.entries.get(&(day, task))
+    }
+
+    /// Overrides a previous entry of the same day and task.
+    pub fn record(&mut self, report: &BenchReport) {
+        self.entries.insert(
+            (report.day, report.task),
+            BaselineEntry {
+                parse: report.parse.median,
+                solve: report.solve.median,
+                mean_allocations: report.mean_allocations,
+            },
+        );
+    }
+}
+
+/// Change of the summed up stage medians compared to a baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    pub fn new(baseline: &BaselineEntry, report: &BenchReport) -> Self {
+        Self {
+            baseline: baseline.total(),
+            current: report.parse.median + report.solve.median,
+        }
+    }
+
+    pub fn change_percent(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        (self.current.as_secs_f64() - baseline) / baseline * 100.0
+    }
+
+    pub fn is_slowdown(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn spread_of_durations() {
+        assert_case(&[5], (5, 5, 5));
+        assert_case(&[7, 1, 4], (1, 4, 7));
+        assert_case(&[8, 2, 4, 6], (2, 5, 8));
+        fn assert_case(input: &[u64], (min, median, max): (u64, u64, u64)) {
+            let durations: Vec<Duration> =
+                input.iter().map(|&ms| Duration::from_millis(ms)).collect();
+            let actual = Spread::of(&durations);
+            let expected = Spread {
+                min: Duration::from_millis(min),
+                median: Duration::from_millis(median),
+                max: Duration::from_millis(max),
+            };
+            assert_eq!(expected, actual, "Input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let input = "1 1 1500 2500 3.5\n5 2 10 20 0\n";
+        let baseline = Baseline::parse(input).unwrap();
+        assert_eq!(
+            Some(&BaselineEntry {
+                parse: Duration::from_nanos(1500),
+                solve: Duration::from_nanos(2500),
+                mean_allocations: 3.5
+            }),
+            baseline.get(1, 1)
+        );
+        assert_eq!(input, baseline.render());
+        assert!(Baseline::parse("1 1 1500").is_err());
+    }
+
+    #[test]
+    fn detect_slowdown() {
+        let comparison = Comparison {
+            baseline: Duration::from_millis(100),
+            current: Duration::from_millis(120),
+        };
+        assert!(comparison.is_slowdown(10.0));
+        assert!(!comparison.is_slowdown(25.0));
+    }
+}
@@ -1,30 +1,131 @@
-use std::{io, path::PathBuf, str::FromStr};
+use std::{
+    io::{self, Read},
+    path::PathBuf,
+};
 
-use clap::{Args, Parser};
-#[derive(Debug, Args, Clone)]
-pub struct Input {
-    pub content: String,
-}
+use clap::{Args, Parser, Subcommand};
+
+use crate::error::{AocError, AocResult};
+use crate::input_discovery;
+use crate::report::OutputFormat;
 
-impl FromStr for Input {
-    type Err = io::Error;
+/// At most one source of the puzzle input can be given.
+/// Without any, "day_<N>.txt" is looked up in the input directory.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct InputArgs {
+    /// Reads the puzzle input from this file
+    #[arg(long, group = "source")]
+    pub file: Option<PathBuf>,
+    /// Takes the given text as the puzzle input
+    #[arg(long, group = "source")]
+    pub text: Option<String>,
+    /// Reads the puzzle input from stdin if given as "-"
+    #[arg(value_name = "-", value_parser = ["-"], group = "source")]
+    pub stdin: Option<String>,
+    /// Takes the embedded example of the task as input and verifies its expected answer.
+    /// Looks up "day_<N>_example.txt" in the input directory if the task has no embedded example.
+    #[arg(long, conflicts_with = "source")]
+    pub example: bool,
+    #[arg(long, default_value = input_discovery::DEFAULT_INPUT_DIR)]
+    pub input_dir: PathBuf,
+    /// Passes the input unchanged instead of removing a byte order mark,
+    /// converting CRLF line endings and dropping trailing blank lines
+    #[arg(long)]
+    pub raw_input: bool,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let path = PathBuf::from(s);
-        if path.exists() {
-            let string = std::fs::read_to_string(&path)?;
-            Ok(Self { content: string })
+impl InputArgs {
+    /// The embedded example is only used if the flag "--example" is given.
+    pub fn resolve(self, day: usize, embedded_example: Option<&str>) -> AocResult<String> {
+        if let (true, Some(example)) = (self.example, embedded_example) {
+            Ok(example.to_string())
+        } else if let Some(path) = self.file {
+            std::fs::read_to_string(&path).map_err(|error| AocError::reading_file(&path, error))
+        } else if let Some(text) = self.text {
+            Ok(text)
+        } else if self.stdin.is_some() {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| AocError::io("Could not read input from stdin", error))?;
+            Ok(input)
         } else {
-            Ok(Self {
-                content: String::from(s),
-            })
+            input_discovery::find_input(&self.input_dir, day, self.example)
         }
     }
 }
 
 #[derive(Debug, Parser)]
+pub struct AppCli {
+    #[command(subcommand)]
+    pub command: AppCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AppCommand {
+    /// Solves one task of a day
+    Run(TaskOverCli),
+    /// Solves every registered task with the inputs found in the input directory
+    All(RunAllCli),
+    /// Measures how long one task takes to parse and solve over several iterations
+    Bench(BenchCli),
+    /// Lists every registered day and part with its title
+    List,
+}
+
+#[derive(Debug, Args)]
 pub struct TaskOverCli {
-    pub input: Input,
     pub day: usize,
     pub task: usize,
+    #[command(flatten)]
+    pub input: InputArgs,
+    /// File with known answers as lines of "<day> <task> <answer>" to verify the result against
+    #[arg(long, conflicts_with = "example")]
+    pub answers: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+    /// Prints the grid of the day with the cells relevant to the answer highlighted before the answer.
+    /// Colors are only used if stdout is a terminal.
+    #[arg(long, conflicts_with = "format")]
+    pub dump_grid: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RunAllCli {
+    /// Directory containing the inputs named as "day_<N>.txt"
+    #[arg(long, default_value = input_discovery::DEFAULT_INPUT_DIR)]
+    pub input_dir: PathBuf,
+    /// Runs with the embedded examples and verifies their expected answers.
+    /// Tasks without an embedded example use the inputs named as "day_<N>_example.txt" instead.
+    #[arg(long)]
+    pub example: bool,
+    /// File with known answers as lines of "<day> <task> <answer>" to verify the results against
+    #[arg(long, conflicts_with = "example")]
+    pub answers: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+    /// Passes the inputs unchanged instead of removing a byte order mark,
+    /// converting CRLF line endings and dropping trailing blank lines
+    #[arg(long)]
+    pub raw_input: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchCli {
+    pub day: usize,
+    pub task: usize,
+    #[command(flatten)]
+    pub input: InputArgs,
+    #[arg(long, short = 'n', default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+    /// File of an earlier bench run to compare the medians against
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+    /// Stores the medians of this run into the given file, keeping entries of other tasks
+    #[arg(long)]
+    pub save_baseline: Option<PathBuf>,
+    /// Slowdown in percent compared to the baseline which is reported as a failure
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
 }
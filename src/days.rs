@@ -1,5 +1,33 @@
+use crate::task_handlers::RegisteredDay;
+
 pub mod day_1;
 pub mod day_2;
 pub mod day_3;
 pub mod day_4;
 pub mod day_5;
+
+/// Registers the solver of every day under the day number and tests its embedded examples.
+/// The modules are declared outside the macro so rustfmt still formats them.
+macro_rules! days {
+    ($($day:literal => $module:ident::$solver:ident),* $(,)?) => {
+        pub const ALL_DAYS: &[RegisteredDay] = &[$(RegisteredDay::new::<$module::$solver>($day)),*];
+
+        #[cfg(test)]
+        mod examples {
+            $(
+                #[test]
+                fn $module() {
+                    crate::solver::assert_examples::<super::$module::$solver>();
+                }
+            )*
+        }
+    };
+}
+
+days! {
+    1 => day_1::Day1,
+    2 => day_2::Day2,
+    3 => day_3::Day3,
+    4 => day_4::Day4,
+    5 => day_5::Day5,
+}
@@ -1,31 +1,91 @@
+use crate::error::{AocResult, ParseError};
+use crate::solver::{Answer, Example, Solver};
 use once_cell::sync::Lazy;
 
-pub fn handle(input: String) -> String {
-    let parsed = parse(&input);
-    parsed.into_iter().sum::<u32>().to_string()
-}
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Parsed = Vec<Calibration>;
+
+    const TITLE: &'static str = "Trebuchet?!";
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE_PART_1,
+            answers: &[(1, "142")],
+        },
+        Example {
+            input: EXAMPLE_PART_2,
+            answers: &[(2, "281")],
+        },
+    ];
+
+    /// Blank lines are skipped, every other line needs at least one digit or spelled out digit.
+    fn parse(input: &str) -> AocResult<Self::Parsed> {
+        let calibrations = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let error = |message: &str| {
+                    ParseError::new(message)
+                        .at_line(index + 1)
+                        .in_text(line, 0..line.chars().count())
+                };
+                let spelled = parse_line_task_2(line)
+                    .ok_or_else(|| error("Expected at least one digit or spelled out digit"))?;
+                let digits =
+                    parse_line_task_1(line).ok_or_else(|| error("Expected at least one digit"));
+                Ok(Calibration { digits, spelled })
+            })
+            .collect::<Result<_, ParseError>>()?;
+        Ok(calibrations)
+    }
 
-fn parse(input: &str) -> Vec<u32> {
-    input.lines().map(parse_line_task_1).collect()
+    fn part_one(parsed: &Self::Parsed) -> AocResult<Answer> {
+        let mut sum = 0;
+        for calibration in parsed {
+            sum += calibration.digits.clone()?;
+        }
+        Ok(sum.into())
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> AocResult<Answer> {
+        Ok(parsed
+            .iter()
+            .map(|calibration| calibration.spelled)
+            .sum::<u32>()
+            .into())
+    }
 }
-pub fn handle_task_2(input: String) -> String {
-    input
-        .lines()
-        .enumerate()
-        .map(|(index, value)| {
-            let value = parse_line_task_2(value);
-            value.unwrap_or_else(|| panic!("No 2 digits at line {}", index + 1))
-        })
-        .sum::<u32>()
-        .to_string()
+
+/// Calibration value of one line as read by either part.
+#[derive(Debug)]
+pub struct Calibration {
+    /// From the first and last digit, an error if the line only has spelled out digits.
+    digits: Result<u32, ParseError>,
+    /// From the first and last digit or spelled out digit.
+    spelled: u32,
 }
 
-fn parse_line_task_1(input: &str) -> u32 {
+const EXAMPLE_PART_1: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+const EXAMPLE_PART_2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+fn parse_line_task_1(input: &str) -> Option<u32> {
     let iter = input
         .trim()
         .chars()
         .filter_map(|may_digit| may_digit.to_digit(10));
-    calc_numbers(iter).unwrap()
+    calc_numbers(iter)
 }
 
 fn calc_numbers(iterator: impl IntoIterator<Item = u32>) -> Option<u32> {
@@ -39,30 +99,27 @@ fn calc_numbers(iterator: impl IntoIterator<Item = u32>) -> Option<u32> {
 }
 
 fn parse_line_task_2(input: &str) -> Option<u32> {
-    let start: u32 = iterate_from(input.chars(), |buffer, next| buffer.push(next));
+    let start: u32 = iterate_from(input.chars(), |buffer, next| buffer.push(next))?;
     let last: u32 = iterate_from(input.chars().rev(), |buffer, next| {
-        *buffer = next.to_string() + &buffer;
-    });
+        *buffer = next.to_string() + buffer;
+    })?;
     return Some(last + start * 10);
     fn iterate_from(
         input: impl Iterator<Item = char>,
         on_buffer_change: impl Fn(&mut String, char),
-    ) -> u32 {
+    ) -> Option<u32> {
         let mut buffer = String::default();
 
         for next in input {
             on_buffer_change(&mut buffer, next);
-            match CHARS_TO_NUMBER
+            if let Some((_, index)) = CHARS_TO_NUMBER
                 .iter()
                 .find(|(word, _)| buffer.contains(word.as_ref()))
             {
-                Some((_, index)) => {
-                    return *index;
-                }
-                None => (),
+                return Some(*index);
             }
         }
-        0
+        None
     }
 }
 
@@ -89,6 +146,20 @@ static CHARS_TO_NUMBER: Lazy<Vec<(Box<str>, u32)>> = Lazy::new(|| {
 mod testing {
     use super::*;
 
+    #[test]
+    fn reject_line_without_digit() {
+        assert_eq!(
+            "Could not parse input at line 3, column 1: Expected at least one digit or spelled out digit",
+            Day1::parse("1a\n\nabc").unwrap_err().to_string()
+        );
+        let parsed = Day1::parse("1a\n\none").unwrap();
+        assert_eq!(Answer::Unsigned(22), Day1::part_two(&parsed).unwrap());
+        assert_eq!(
+            "Could not parse input at line 3, column 1: Expected at least one digit",
+            Day1::part_one(&parsed).unwrap_err().to_string()
+        );
+    }
+
     #[test]
     fn parsing_day_1_line_task_2() {
         assert_case("two1nine", 29);
@@ -145,7 +216,11 @@ mod testing {
             vec![12, 38, 15, 77],
         );
         fn assert_case(input: &str, expected: Vec<u32>) {
-            let actual = parse(input);
+            let actual: Vec<u32> = Day1::parse(input)
+                .unwrap()
+                .into_iter()
+                .map(|calibration| calibration.digits.unwrap())
+                .collect();
             assert_eq!(
                 expected, actual,
                 "Expected: {:?}, Actual: {:?}",
@@ -159,7 +234,7 @@ mod testing {
         assert_case("a1b2c3d4e5f", 15);
         assert_case("treb7uchet", 77);
         fn assert_case(input: &str, expected: u32) {
-            let actual = parse_line_task_1(input);
+            let actual = parse_line_task_1(input).unwrap();
             assert_eq!(
                 expected, actual,
                 "Expected: {:?}, Actual: {:?}",
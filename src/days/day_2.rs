@@ -1,48 +1,66 @@
-pub fn handle_task(input: String) -> String {
-    let parsed = parse_input(&input);
-    let all_invalid = get_all_valid_games(
-        &parsed,
-        &Set {
-            red: 12,
-            blue: 14,
-            green: 13,
-        },
-    );
-    all_invalid.into_iter().sum::<u32>().to_string()
-}
-pub fn handle_task_2(input: String) -> String {
-    let parsed = parse_input(&input);
-    let maximums: Vec<Set> = parsed
-        .into_iter()
-        .map(|game| {
-            game.sets.into_iter().fold(Set::default(), |max, next| Set {
-                red: max.red.max(next.red),
-                blue: max.blue.max(next.blue),
-                green: max.green.max(next.green),
+use crate::error::{AocResult, ParseError};
+use crate::solver::{Answer, Example, Solver};
+use crate::utils::parsing::{
+    keyword, literal, map, padded, pair, parse_lines, preceded, separated, unsigned, Parser,
+};
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Parsed = Vec<Game>;
+
+    const TITLE: &'static str = "Cube Conundrum";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answers: &[(1, "8"), (2, "2286")],
+    }];
+
+    fn parse(input: &str) -> AocResult<Self::Parsed> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> AocResult<Answer> {
+        let all_invalid = get_all_valid_games(
+            parsed,
+            &Set {
+                red: 12,
+                blue: 14,
+                green: 13,
+            },
+        );
+        Ok(all_invalid.into_iter().sum::<u32>().into())
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> AocResult<Answer> {
+        let maximums: Vec<Set> = parsed
+            .iter()
+            .map(|game| {
+                game.sets.iter().fold(Set::default(), |max, next| Set {
+                    red: max.red.max(next.red),
+                    blue: max.blue.max(next.blue),
+                    green: max.green.max(next.green),
+                })
             })
-        })
-        .collect();
-    maximums
-        .into_iter()
-        .map(|max_set| max_set.red * max_set.blue * max_set.green)
-        .sum::<u32>()
-        .to_string()
+            .collect();
+        Ok(maximums
+            .into_iter()
+            .map(|max_set| max_set.red * max_set.blue * max_set.green)
+            .sum::<u32>()
+            .into())
+    }
 }
+const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: u32,
     sets: Vec<Set>,
 }
 
-impl Game {
-    fn new(id: u32) -> Self {
-        Self {
-            id,
-            sets: Default::default(),
-        }
-    }
-}
-
 #[derive(Default, Debug)]
 struct Set {
     red: u32,
@@ -50,13 +68,14 @@ struct Set {
     green: u32,
 }
 
-fn parse_input(input: &str) -> Vec<Game> {
-    input.lines().map(parse_game_line_input).collect()
+/// Blank lines are skipped.
+fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse_lines(&game_line(), input)
 }
 
 fn get_all_valid_games(games: &[Game], conf: &Set) -> Vec<u32> {
     games
-        .into_iter()
+        .iter()
         .filter_map(|game| {
             if is_valid_game(game, conf) {
                 Some(game.id)
@@ -73,49 +92,70 @@ fn is_valid_game(game: &Game, conf: &Set) -> bool {
         .all(|set| set.red <= conf.red && set.blue <= conf.blue && set.green <= conf.green)
 }
 
-fn parse_game_line_input(line: &str) -> Game {
-    let trimmed = line.trim();
-    let mut splitted = trimmed.split(":");
-    match (splitted.next(), splitted.next()) {
-        (Some(game), Some(sets)) => {
-            let id: u32 = game.strip_prefix("Game ").unwrap().parse().unwrap();
-            let splitted = sets.split(";");
-            let mut game = Game::new(id);
-            for next in splitted {
-                let comma_splitted = next.split(",");
-                let mut set = Set::default();
-                for next_comma in comma_splitted {
-                    let mut space_seperated = next_comma.trim().split_whitespace();
-                    match (space_seperated.next(), space_seperated.next()) {
-                        (Some(number), Some(keyword)) => {
-                            let number: u32 = number.parse().unwrap();
-                            match keyword {
-                                "red" => set.red = number,
-                                "blue" => set.blue = number,
-                                "green" => set.green = number,
-                                _ => unimplemented!(),
-                            }
-                        }
-                        _ => unimplemented!(),
-                    }
+/// "Game <id>: <number> <color>, ...; ..." with red, blue or green as colors.
+fn game_line() -> impl Parser<Game> {
+    let cubes = pair(padded(unsigned::<u32>), padded(keyword(COLORS)));
+    let set = map(separated(cubes, literal(",")), |cubes| {
+        cubes
+            .into_iter()
+            .fold(Set::default(), |mut set, (number, color)| {
+                match color {
+                    Color::Red => set.red = number,
+                    Color::Blue => set.blue = number,
+                    Color::Green => set.green = number,
                 }
-                game.sets.push(set);
-            }
-            game
-        }
-        _ => unimplemented!(),
-    }
+                set
+            })
+    });
+    let id = preceded(literal("Game"), padded(unsigned::<u32>));
+    let sets = preceded(literal(":"), separated(set, literal(";")));
+    map(pair(id, sets), |(id, sets)| Game { id, sets })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Color {
+    Red,
+    Blue,
+    Green,
 }
 
+const COLORS: &[(&str, Color)] = &[
+    ("red", Color::Red),
+    ("blue", Color::Blue),
+    ("green", Color::Green),
+];
+
 #[cfg(test)]
 mod testing {
     use super::*;
+    use crate::utils::parsing::parse_complete;
 
     #[test]
     fn test_parsing() {
-        let actual = parse_game_line_input(
+        let actual = parse_complete(
+            &game_line(),
             "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-        );
+        )
+        .unwrap();
         insta::assert_debug_snapshot!(actual);
     }
+
+    #[test]
+    fn reject_invalid_game_lines() {
+        assert_case("Game 1 3 blue");
+        assert_case("Gam 1: 3 blue");
+        assert_case("Game 1: 3 purple");
+        assert_case("Game 1: 3 blue, red");
+        assert_case("Game 1: x blue");
+        fn assert_case(input: &str) {
+            let actual = parse_complete(&game_line(), input);
+            assert!(actual.is_err(), "Input: {}", input);
+        }
+    }
+
+    #[test]
+    fn report_line_of_invalid_game() {
+        let actual = parse_input("Game 1: 3 blue\n\nGame 2: 1 pink").unwrap_err();
+        assert_eq!(Some(3), actual.line());
+    }
 }
@@ -1,22 +1,62 @@
+use crate::error::{AocResult, ParseError};
 use crate::prelude::*;
+use crate::solver::{Answer, Example, Solver};
+use crate::utils::grid_2d::{Connectivity, Region, TextGridOptions};
 use std::collections::HashSet;
 
-pub fn handle_task(input: String) -> String {
-    let parsed = parse(&input);
-    let part_numbers = get_all_part_number(&parsed);
-    let part_numbers_vals = calc_part_numbers(&parsed, &part_numbers);
-    part_numbers_vals.into_iter().sum::<usize>().to_string()
-}
-pub fn handle_task_2(input: String) -> String {
-    let parsed = parse(&input);
-    get_part_number_gears(&parsed)
-        .into_iter()
-        .map(|(left, right)| calc_part_numbers(&parsed, &[left, right]))
-        .map(|numbers| numbers.into_iter().product::<usize>())
-        .sum::<usize>()
-        .to_string()
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Parsed = Grid<Symbol>;
+
+    const TITLE: &'static str = "Gear Ratios";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answers: &[(1, "4361"), (2, "467835")],
+    }];
+
+    fn parse(input: &str) -> AocResult<Self::Parsed> {
+        Ok(parse(input)?)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> AocResult<Answer> {
+        let part_numbers = get_all_part_number(parsed);
+        let part_numbers_vals = calc_part_numbers(parsed, &part_numbers);
+        Ok(part_numbers_vals.into_iter().sum::<usize>().into())
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> AocResult<Answer> {
+        Ok(get_part_number_gears(parsed)
+            .into_iter()
+            .map(|(left, right)| calc_part_numbers(parsed, &[left, right]))
+            .map(|numbers| numbers.into_iter().product::<usize>())
+            .sum::<usize>()
+            .into())
+    }
+
+    /// Highlights the digits of all part numbers and every gear.
+    fn render_annotated(parsed: &Self::Parsed, style: RenderStyle) -> Option<String> {
+        let gears = get_gears(parsed, &get_all_numbers(parsed));
+        let highlighted: HashSet<Coords> = get_all_part_number(parsed)
+            .iter()
+            .flat_map(ColumnRange::cells)
+            .chain(gears)
+            .collect();
+        Some(parsed.render_highlighted(Symbol::to_char, &highlighted, style))
+    }
 }
 
+const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Symbol {
     Symb(char),
@@ -24,47 +64,63 @@ pub enum Symbol {
     Nothing,
 }
 
+impl Symbol {
+    fn to_char(&self) -> char {
+        match self {
+            Symbol::Symb(symbol) => *symbol,
+            Symbol::Digit(digit) => char::from_digit(*digit, 10).unwrap(),
+            Symbol::Nothing => '.',
+        }
+    }
+}
+
 impl From<char> for Symbol {
     fn from(value: char) -> Self {
         match value {
             '.' => Self::Nothing,
-            sym if sym.is_digit(10) => Self::Digit(sym.to_digit(10).unwrap()),
+            sym if sym.is_ascii_digit() => Self::Digit(sym.to_digit(10).unwrap()),
             sym => Self::Symb(sym),
         }
     }
 }
-fn get_part_number_gears(grid: &Array2D<Symbol>) -> Vec<(ColumnRange, ColumnRange)> {
-    let mut already_found: HashSet<Coords> = HashSet::new();
+/// Pairs of part numbers around every '*' which touches exactly 2 of them.
+fn get_part_number_gears(grid: &Grid<Symbol>) -> Vec<(ColumnRange, ColumnRange)> {
+    let numbers = get_all_numbers(grid);
+    get_gears(grid, &numbers)
+        .into_iter()
+        .map(|gear| {
+            let mut around = numbers.iter().filter(|number| number.touches(gear));
+            (
+                around.next().unwrap().clone(),
+                around.next().unwrap().clone(),
+            )
+        })
+        .collect()
+}
+
+fn get_gears(grid: &Grid<Symbol>, numbers: &[ColumnRange]) -> Vec<Coords> {
     get_all_symbols(grid)
         .into_iter()
-        .filter(|coords| matches!(grid_2d::access_grid(grid, *coords), Symbol::Symb('*')))
-        .filter_map(|coords| {
-            let might_be_two = get_part_number_around(grid, &mut already_found, coords);
-            if might_be_two.len() == 2 {
-                Some((
-                    might_be_two.get(0).unwrap().clone(),
-                    might_be_two.get(1).unwrap().clone(),
-                ))
-            } else {
-                None
-            }
+        .filter(|coords| matches!(grid[*coords], Symbol::Symb('*')))
+        .filter(|coords| {
+            numbers
+                .iter()
+                .filter(|number| number.touches(*coords))
+                .count()
+                == 2
         })
         .collect()
 }
 
-fn parse(input: &str) -> Array2D<Symbol> {
-    grid_2d::text_to_grid(input, Symbol::from)
+fn parse(input: &str) -> Result<Grid<Symbol>, ParseError> {
+    grid_2d::try_text_to_grid(input, &TextGridOptions::trimmed(), Symbol::from)
 }
 
-fn get_all_symbols(grid: &Array2D<Symbol>) -> Vec<Coords> {
-    let mut all_coords = Vec::default();
-    for coords in grid_2d::all_coords(grid) {
-        let val = grid_2d::access_grid(grid, coords);
-        if matches!(val, Symbol::Symb(_)) {
-            all_coords.push(coords);
-        }
-    }
-    all_coords
+fn get_all_symbols(grid: &Grid<Symbol>) -> Vec<Coords> {
+    grid.iter()
+        .filter(|(_, symbol)| matches!(symbol, Symbol::Symb(_)))
+        .map(|(coords, _)| coords)
+        .collect()
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -74,21 +130,39 @@ struct ColumnRange {
     end: usize,
 }
 
-fn calc_part_numbers(grid: &Array2D<Symbol>, columns: &[ColumnRange]) -> Vec<usize> {
+impl ColumnRange {
+    fn cells(&self) -> impl Iterator<Item = Coords> + '_ {
+        (self.start..=self.end).map(|x| Coords { y: self.row, x })
+    }
+
+    /// True if the coordinate is next to one of the cells, diagonally included.
+    fn touches(&self, coords: Coords) -> bool {
+        coords.y.abs_diff(self.row) <= 1 && coords.x + 1 >= self.start && coords.x <= self.end + 1
+    }
+}
+
+impl From<Region> for ColumnRange {
+    fn from(value: Region) -> Self {
+        Self {
+            row: value.top_left.y,
+            start: value.top_left.x,
+            end: value.bottom_right.x,
+        }
+    }
+}
+
+fn calc_part_numbers(grid: &Grid<Symbol>, columns: &[ColumnRange]) -> Vec<usize> {
     let mut calc_numbers = Vec::new();
 
     for next_range in columns {
         let mut factor = 1;
         let mut number = 0;
         for next_number in (next_range.start..=next_range.end).rev() {
-            if let Symbol::Digit(numeric_val) = grid_2d::access_grid(
-                grid,
-                Coords {
-                    x: next_number,
-                    y: next_range.row,
-                },
-            ) {
-                number += factor * (*numeric_val) as usize;
+            if let Symbol::Digit(numeric_val) = grid[Coords {
+                x: next_number,
+                y: next_range.row,
+            }] {
+                number += factor * numeric_val as usize;
                 factor *= 10;
             } else {
                 unreachable!()
@@ -99,157 +173,35 @@ fn calc_part_numbers(grid: &Array2D<Symbol>, columns: &[ColumnRange]) -> Vec<usi
     calc_numbers
 }
 
-fn get_part_number_around(
-    grid: &Array2D<Symbol>,
-    already_found: &mut HashSet<Coords>,
-    coords: Coords,
-) -> Vec<ColumnRange> {
-    let mut output = Vec::new();
-
-    travers_and_update(
-        grid,
-        already_found,
-        coords,
-        |grid, coords| grid_2d::go_left(grid, coords),
-        &mut output,
-    );
-    travers_and_update(
-        grid,
-        already_found,
-        coords,
-        |grid, coords| grid_2d::go_right(grid, coords),
-        &mut output,
-    );
-    travers_and_update(
-        grid,
-        already_found,
-        coords,
-        |grid, coords| grid_2d::go_up(grid, coords),
-        &mut output,
-    );
-    travers_and_update(
-        grid,
-        already_found,
-        coords,
-        |grid, coords| grid_2d::go_down(grid, coords),
-        &mut output,
-    );
-    travers_and_update(
-        grid,
-        already_found,
-        coords,
-        |grid, coords| grid_2d::go_left_up(grid, coords),
-        &mut output,
-    );
-    travers_and_update(
-        grid,
-        already_found,
-        coords,
-        |grid, coords| grid_2d::go_left_down(grid, coords),
-        &mut output,
-    );
-    travers_and_update(
-        grid,
-        already_found,
-        coords,
-        |grid, coords| grid_2d::go_right_up(grid, coords),
-        &mut output,
-    );
-    travers_and_update(
-        grid,
-        already_found,
-        coords,
-        |grid, coords| grid_2d::go_right_down(grid, coords),
-        &mut output,
-    );
-    return output;
-    fn travers_and_update(
-        grid: &Array2D<Symbol>,
-        already_found: &mut HashSet<Coords>,
-        coords: Coords,
-        on_start: impl Fn(&Array2D<Symbol>, Coords) -> grid_2d::MaybeNextCell<Symbol>,
-        output: &mut Vec<ColumnRange>,
-    ) {
-        if let Some(to_add) = travers_colums_at(grid, already_found, coords, on_start) {
-            output.push(to_add);
-        }
-    }
-    fn travers_colums_at(
-        grid: &Array2D<Symbol>,
-        already_found: &mut HashSet<Coords>,
-        coords: Coords,
-        on_start: impl Fn(&Array2D<Symbol>, Coords) -> grid_2d::MaybeNextCell<Symbol>,
-    ) -> Option<ColumnRange> {
-        let (sym, start_coords) = on_start(grid, coords)?;
-        if let Symbol::Digit(_) = sym {
-            let (row, column) = (start_coords.y, start_coords.x);
-            if !already_found.insert(start_coords) {
-                return None;
-            }
-            let mut range = ColumnRange {
-                row,
-                start: column,
-                end: column,
-            };
-
-            let mut current_coords = start_coords;
-            while let Some((Symbol::Digit(_), Coords { x, .. })) =
-                grid_2d::go_left(grid, current_coords)
-            {
-                current_coords = Coords { y: row, x };
-                if !already_found.insert(current_coords) {
-                    break;
-                }
-
-                range.start = x;
-            }
-
-            current_coords = start_coords;
-            while let Some((Symbol::Digit(_), Coords { x, .. })) =
-                grid_2d::go_right(grid, current_coords)
-            {
-                current_coords = Coords { y: row, x };
-                if !already_found.insert(current_coords) {
-                    break;
-                }
-
-                range.end = x;
-            }
-
-            Some(range)
-        } else {
-            None
-        }
-    }
+/// Every number in the grid as a run of digits within one row.
+fn get_all_numbers(grid: &Grid<Symbol>) -> Vec<ColumnRange> {
+    grid_2d::connected_components(grid, Connectivity::RowRuns, |symbol| {
+        matches!(symbol, Symbol::Digit(_))
+    })
+    .into_iter()
+    .map(ColumnRange::from)
+    .collect()
 }
-fn get_all_part_number(grid: &Array2D<Symbol>) -> Vec<ColumnRange> {
-    let mut already_found: HashSet<Coords> = HashSet::new();
-    let mut output = Vec::new();
-    for coords in get_all_symbols(grid) {
-        let to_add = get_part_number_around(grid, &mut already_found, coords);
-        output.extend(to_add.into_iter());
-    }
 
-    return output;
+/// Row runs of digits adjacent to a symbol.
+fn get_all_part_number(grid: &Grid<Symbol>) -> Vec<ColumnRange> {
+    get_all_numbers(grid)
+        .into_iter()
+        .filter(|number| {
+            number.cells().any(|coords| {
+                grid.neighbors8(coords)
+                    .any(|(_, symbol)| matches!(symbol, Symbol::Symb(_)))
+            })
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod testing {
     use super::*;
-
-    const TEST_INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
     #[test]
     fn parsing_day_3_input_all_symbols_find() {
-        let parsed = parse(TEST_INPUT);
+        let parsed = parse(EXAMPLE).unwrap();
         let mut all_sym_coords = get_all_symbols(&parsed);
         let mut expected: Vec<Coords> = vec![
             (1, 3).into(),
@@ -307,7 +259,7 @@ mod testing {
     ];
     #[test]
     fn parsing_day_3_all_part_number() {
-        let parsed = parse(TEST_INPUT);
+        let parsed = parse(EXAMPLE).unwrap();
         let mut part_numbers = get_all_part_number(&parsed);
         let mut expected: Vec<ColumnRange> = COLUMS_RANGES_INPUT.to_vec();
         part_numbers.sort();
@@ -317,7 +269,7 @@ mod testing {
     }
     #[test]
     fn day_3_clac_numbers_from_part_number() {
-        let parsed = parse(TEST_INPUT);
+        let parsed = parse(EXAMPLE).unwrap();
         let mut actual = calc_part_numbers(&parsed, COLUMS_RANGES_INPUT);
 
         let mut expected: Vec<usize> = vec![35, 467, 633, 617, 592, 755, 664, 598];
@@ -326,13 +278,32 @@ mod testing {
 
         assert_eq!(&expected, &actual);
     }
+    #[test]
+    fn day_3_highlight_part_numbers_and_gears() {
+        let parsed = parse(EXAMPLE).unwrap();
+        let plain = Day3::render_annotated(&parsed, RenderStyle::Plain).unwrap();
+        assert_eq!(format!("{}\n", EXAMPLE), plain);
+        let colored = Day3::render_annotated(&parsed, RenderStyle::Ansi).unwrap();
+        let first_lines: Vec<&str> = colored.lines().take(2).collect();
+        assert_eq!(
+            vec!["\x1b[1;31m467\x1b[0m..114..", "...\x1b[1;31m*\x1b[0m......"],
+            first_lines
+        );
+    }
+
+    #[test]
+    fn day_3_report_ragged_line() {
+        let actual = parse("467..\n...*.\n..3\n").unwrap_err();
+        assert_eq!(Some(3), actual.line());
+    }
+
     #[test]
     fn day_3_get_gear_part_number() {
-        let parsed = parse(TEST_INPUT);
+        let parsed = parse(EXAMPLE).unwrap();
 
-        let mut actual = get_part_number_gears(&parsed);
+        let actual = get_part_number_gears(&parsed);
 
-        let mut expected: Vec<(ColumnRange, ColumnRange)> = vec![
+        let expected: Vec<(ColumnRange, ColumnRange)> = vec![
             (
                 ColumnRange {
                     row: 2,
@@ -358,9 +329,22 @@ mod testing {
                 },
             ),
         ];
-        expected.sort();
-        actual.sort();
+        let (expected, actual) = (normalized(expected), normalized(actual));
 
+        fn normalized(pairs: Vec<(ColumnRange, ColumnRange)>) -> Vec<(ColumnRange, ColumnRange)> {
+            let mut pairs: Vec<(ColumnRange, ColumnRange)> = pairs
+                .into_iter()
+                .map(|(left, right)| {
+                    if left <= right {
+                        (left, right)
+                    } else {
+                        (right, left)
+                    }
+                })
+                .collect();
+            pairs.sort();
+            pairs
+        }
         assert_eq!(&expected, &actual);
     }
 }
@@ -1,41 +1,75 @@
+use crate::error::{AocError, AocResult, ParseError};
+use crate::solver::{Answer, Example, Solver};
+use crate::utils::parsing::{
+    literal, many, map, padded, pair, parse_lines, preceded, terminated, unsigned, Parser,
+};
 use std::rc::Rc;
 
-pub fn handle_task(input: String) -> String {
-    let parsed = parse(&input);
-    parsed
-        .iter()
-        .map(get_seqs)
-        .map(|seqs| calculate_score_of(&seqs))
-        .sum::<u32>()
-        .to_string()
-}
-pub fn handle_task_2(input: String) -> String {
-    let parsed = parse(&input);
-    let mut plot: Vec<(usize, Vec<usize>)> = parsed
-        .iter()
-        .map(get_seqs)
-        .enumerate()
-        .map(|(index, seqs)| (1, next_seq(index, &seqs)))
-        .collect();
-    plot.first_mut().unwrap().0 = 1;
+pub struct Day4;
 
-    for index in 0..plot.len() {
-        let (count, seq) = plot.get(index).unwrap().clone();
+impl Solver for Day4 {
+    type Parsed = Vec<Card>;
 
-        for &next_card in seq.iter() {
-            plot.get_mut(next_card).unwrap().0 += count;
-        }
+    const TITLE: &'static str = "Scratchcards";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answers: &[(1, "13"), (2, "30")],
+    }];
+
+    fn parse(input: &str) -> AocResult<Self::Parsed> {
+        Ok(parse(input)?)
     }
 
-    let sum = plot.into_iter().fold(0usize, |acc, (count, _)| acc + count);
+    fn part_one(parsed: &Self::Parsed) -> AocResult<Answer> {
+        Ok(parsed
+            .iter()
+            .map(get_seqs)
+            .map(|seqs| calculate_score_of(&seqs))
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> AocResult<Answer> {
+        let mut plot: Vec<(usize, Vec<usize>)> = parsed
+            .iter()
+            .map(get_seqs)
+            .enumerate()
+            .map(|(index, seqs)| (1, next_seq(index, &seqs)))
+            .collect();
+
+        for index in 0..plot.len() {
+            let (count, seq) = plot[index].clone();
+
+            for &next_card in seq.iter() {
+                let won_card = plot.get_mut(next_card).ok_or_else(|| {
+                    AocError::invalid_input(format!(
+                        "Card {} wins a copy of card {} after the last card",
+                        index + 1,
+                        next_card + 1
+                    ))
+                })?;
+                won_card.0 += count;
+            }
+        }
+
+        let sum = plot.into_iter().fold(0usize, |acc, (count, _)| acc + count);
 
-    return sum.to_string();
-    fn next_seq(index: usize, seqs: &[u32]) -> Vec<usize> {
-        let start = index + 1;
-        let end = start + seqs.len();
-        (start..end).collect()
+        return Ok(sum.into());
+        fn next_seq(index: usize, seqs: &[u32]) -> Vec<usize> {
+            let start = index + 1;
+            let end = start + seqs.len();
+            (start..end).collect()
+        }
     }
 }
+
+const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
 fn get_seqs(cards: &Card) -> Rc<[u32]> {
     cards
         .actual
@@ -56,28 +90,24 @@ fn calculate_score_of(points: &[u32]) -> u32 {
     val
 }
 
-fn parse(input: &str) -> Vec<Card> {
-    input
-        .lines()
-        .map(|line| {
-            let mut numbers = line.trim().split(":").skip(1).next().unwrap().split("|");
-
-            let (winning, actual) = (numbers.next().unwrap(), numbers.next().unwrap());
-            let winning = split_numbers(winning);
-            let actual = split_numbers(actual);
-            return Card { winning, actual };
-            fn split_numbers(input: &str) -> Vec<u32> {
-                input
-                    .split_whitespace()
-                    .map(|number| number.parse::<u32>().unwrap())
-                    .collect()
-            }
-        })
-        .collect()
+/// Blank lines are skipped.
+fn parse(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_lines(&card(), input)
+}
+
+/// "Card <id>: <winning numbers> | <actual numbers>"
+fn card() -> impl Parser<Card> {
+    let numbers = || many(padded(unsigned::<u32>));
+    let id = pair(literal("Card"), padded(unsigned::<u32>));
+    let numbers = pair(terminated(numbers(), literal("|")), numbers());
+    map(
+        preceded(pair(id, literal(":")), numbers),
+        |(winning, actual)| Card { winning, actual },
+    )
 }
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     winning: Vec<u32>,
     actual: Vec<u32>,
 }
@@ -88,18 +118,28 @@ mod testing {
     #[test]
     fn day_4_parse() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let parsed = parse(input);
+        let parsed = parse(input).unwrap();
         insta::assert_debug_snapshot!(parsed);
     }
     #[test]
     fn day_4_get_seqs() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let parsed = parse(input);
-        let actual = get_seqs(parsed.get(0).unwrap());
+        let parsed = parse(input).unwrap();
+        let actual = get_seqs(parsed.first().unwrap());
         let expected: Vec<u32> = vec![83, 86, 17, 48];
         assert_eq!(expected.as_slice(), actual.as_ref());
     }
     #[test]
+    fn day_4_reject_invalid_cards() {
+        assert_case("Card 1 41 48 | 83 86");
+        assert_case("Card 1: 41 48 83 86");
+        assert_case("Card 1: 41 x | 83 86");
+        fn assert_case(input: &str) {
+            let actual = parse(input);
+            assert!(actual.is_err(), "Input: {}", input);
+        }
+    }
+    #[test]
     fn day_4_get_score_of() {
         assert_case(&[1, 2, 3, 4], 8);
         assert_case(&[], 0);
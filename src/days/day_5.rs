@@ -1,38 +1,95 @@
 use std::{cmp::Reverse, rc::Rc};
 
-use crate::utils;
+use crate::error::{AocError, AocResult, ParseError};
+use crate::solver::{Answer, Example, PartSolver, Solver};
+use crate::utils::parsing::{
+    many, padded, parse_complete, try_map, unsigned, unsigned_numbers, Section, Sections,
+};
 type ImmutableSeq<T> = Rc<[T]>;
 type ImmutableNumberSeq = ImmutableSeq<NumericVal>;
 type NumericVal = u64;
 
-pub fn handle_task(input: String) -> String {
-    let parsed = parsing(&input);
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Parsed = TableMapping;
+
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        answers: &[(1, "35"), (2, "46"), (3, "46")],
+    }];
+
+    fn parse(input: &str) -> AocResult<Self::Parsed> {
+        Ok(parsing(input)?)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> AocResult<Answer> {
+        handle_task(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> AocResult<Answer> {
+        handle_task_2(parsed)
+    }
+
+    fn additional_parts() -> &'static [(&'static str, PartSolver<Self::Parsed>)] {
+        &[("Part two over merged seed ranges", handle_task_3)]
+    }
+}
+
+const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+fn handle_task(parsed: &TableMapping) -> AocResult<Answer> {
     let locations: ImmutableNumberSeq = parsed
         .seeds
-        .clone()
-        .into_iter()
-        .map(|&next_seed| get_seed_to_location_path(next_seed, &parsed))
+        .iter()
+        .map(|&next_seed| get_seed_to_location_path(next_seed, parsed))
         .collect();
-    let minimal_location = locations.into_iter().min().unwrap();
-    minimal_location.to_string()
+    let minimal_location = locations
+        .iter()
+        .min()
+        .ok_or_else(|| AocError::invalid_input("No seeds given"))?;
+    Ok((*minimal_location).into())
 }
 #[derive(Debug, Default, Clone, PartialEq, Eq, Copy)]
 pub struct ItemRange {
     start: u64,
     end: u64,
 }
-pub fn handle_task_3(input: String) -> String {
-    let parsed = parsing(&input);
-    let (odd, even) = (parsed.seeds.clone(), parsed.seeds.clone());
-    let mut previous: Vec<ItemRange> = even
-        .into_iter()
-        .step_by(2)
-        .zip(odd.into_iter().skip(1).step_by(2))
-        .map(|(&start, &range)| {
-            let end = ((start) + range) - 1;
-            ItemRange { start, end }
-        })
-        .collect();
+fn handle_task_3(parsed: &TableMapping) -> AocResult<Answer> {
+    let mut previous: Vec<ItemRange> = seed_ranges(&parsed.seeds)?;
     for next_step in [
         &parsed.seeds_to_soil,
         &parsed.soil_to_fertilizer,
@@ -44,57 +101,82 @@ pub fn handle_task_3(input: String) -> String {
     ] {
         let merged = merge_ranges(previous);
         let mut current = Vec::new();
-        let mut upper_bound = NumericVal::MIN;
-        let mut lower_bound = NumericVal::MAX;
         for next_range in merged.iter() {
-            for next_row in next_step.clone().into_iter() {
-                upper_bound = upper_bound.max(next_row.source_end);
-                lower_bound = lower_bound.min(next_row.source_start);
-                let mapped = get_mapped_range_from(*next_range, next_row);
-                if let Some(to_push) = mapped {
-                    current.push(to_push);
+            // Rows are sorted by their source start, so the parts of the range
+            // which no row covers are the gaps between them and pass through unchanged.
+            let mut uncovered_from = next_range.start;
+            for next_row in next_step.iter() {
+                if let Some(mapped) = get_mapped_range_from(*next_range, next_row) {
+                    current.push(mapped);
                 }
+                if next_row.source_start > uncovered_from && uncovered_from <= next_range.end {
+                    current.push(ItemRange {
+                        start: uncovered_from,
+                        end: (next_row.source_start - 1).min(next_range.end),
+                    });
+                }
+                uncovered_from = uncovered_from.max(next_row.source_end.saturating_add(1));
             }
-            if upper_bound < next_range.end {
-                current.push(ItemRange {
-                    start: upper_bound + 1,
-                    end: next_range.end,
-                })
-            }
-            if lower_bound > next_range.start {
+            if uncovered_from <= next_range.end {
                 current.push(ItemRange {
-                    start: lower_bound.saturating_sub(1),
+                    start: uncovered_from,
                     end: next_range.end,
-                })
+                });
             }
         }
 
-        previous = current.clone();
-        dbg!(&previous);
+        previous = current;
     }
     let miminum_val = previous
         .into_iter()
         .fold(NumericVal::MAX, |acc, next| acc.min(next.start));
 
-    miminum_val.to_string()
+    Ok(miminum_val.into())
 }
-pub fn handle_task_2(input: String) -> String {
-    let parsed = parsing(&input);
-    let (odd, even) = (parsed.seeds.clone(), parsed.seeds.clone());
+fn handle_task_2(parsed: &TableMapping) -> AocResult<Answer> {
     let mut minum_val = NumericVal::MAX;
-    even.into_iter()
-        .step_by(2)
-        .zip(odd.into_iter().skip(1).step_by(2))
-        .for_each(|(&start, &range)| {
-            let end = (start) + range;
-
-            for next_seed in start..end {
-                let location = get_seed_to_location_path(next_seed, &parsed);
-                minum_val = minum_val.min(location);
-            }
-        });
+    seed_ranges(&parsed.seeds)?.into_iter().for_each(|range| {
+        for next_seed in range.start..=range.end {
+            let location = get_seed_to_location_path(next_seed, parsed);
+            minum_val = minum_val.min(location);
+        }
+    });
 
-    return format!("{:#?}", minum_val);
+    Ok(minum_val.into())
+}
+
+/// Seeds read as pairs of start and length, every length above 0.
+fn seed_ranges(seeds: &[NumericVal]) -> AocResult<Vec<ItemRange>> {
+    if seeds.is_empty() {
+        return Err(AocError::invalid_input("No seeds given"));
+    }
+    if seeds.len() % 2 == 1 {
+        return Err(AocError::invalid_input(format!(
+            "Expected seeds as pairs of \"<start> <length>\", found no length after {}",
+            seeds[seeds.len() - 1]
+        )));
+    }
+    seeds
+        .chunks(2)
+        .map(|pair| {
+            let (start, length) = (pair[0], pair[1]);
+            let last_offset = length.checked_sub(1).ok_or_else(|| {
+                AocError::invalid_input(format!(
+                    "Expected seed ranges with a length above 0, found the length 0 after {}",
+                    start
+                ))
+            })?;
+            let end = start.checked_add(last_offset).ok_or_else(|| {
+                AocError::invalid_input(format!(
+                    "The seed range of {} seeds starting at {} ends above {}",
+                    length,
+                    start,
+                    NumericVal::MAX
+                ))
+            })?;
+            Ok(ItemRange { start, end })
+        })
+        .collect()
 }
 
 pub fn get_seed_to_location_path(seed: NumericVal, mapping: &TableMapping) -> NumericVal {
@@ -115,10 +197,9 @@ pub fn get_seed_to_location_path(seed: NumericVal, mapping: &TableMapping) -> Nu
 }
 
 pub fn get_mapping(source: NumericVal, rows: &ImmutableSeq<RowMapping>) -> NumericVal {
-    return rows
-        .into_iter()
+    rows.iter()
         .find_map(|row| get_withing_range(source, row))
-        .unwrap_or(source);
+        .unwrap_or(source)
 }
 
 fn get_withing_range(val: NumericVal, row: &RowMapping) -> Option<NumericVal> {
@@ -138,7 +219,7 @@ fn merge_ranges(mut to_merge: Vec<ItemRange>) -> Vec<ItemRange> {
     if let Some(mut current_item) = to_merge.next() {
         let mut merged: Vec<ItemRange> = Vec::with_capacity(to_merge.len());
         for next in to_merge {
-            let bound = current_item.end + 1;
+            let bound = current_item.end.saturating_add(1);
             if bound >= next.start {
                 current_item = ItemRange {
                     start: next.start.min(current_item.start),
@@ -185,50 +266,82 @@ fn get_junction_of_ranges(base: ItemRange, other: ItemRange) -> Option<ItemRange
         Some(ItemRange { start, end })
     }
 }
-fn parsing(input: &str) -> TableMapping {
-    let mut chunks = utils::parsing::chunks_of_non_empty_lines(input).into_iter();
-    let seeds = {
-        let line = chunks.next().unwrap();
-        line.into_iter()
-            .next()
-            .unwrap()
-            .split(":")
-            .skip(1)
-            .next()
-            .unwrap()
-            .trim()
-            .split_whitespace()
-            .map(|number| number.parse::<NumericVal>().unwrap())
-            .collect()
-    };
+/// Tables are found by their header "<name> map:" in any order. Other sections are ignored.
+fn parsing(input: &str) -> Result<TableMapping, ParseError> {
+    let sections = Sections::parse(input)?;
+    let seeds_section = sections.require("seeds")?;
+    reject_lines_below(seeds_section)?;
+    let seeds = unsigned_numbers::<NumericVal>(seeds_section.inline).map_err(|error| {
+        let (line_number, header) = seeds_section.header;
+        error
+            .within_line(header, inline_offset(seeds_section))
+            .at_line(line_number)
+    })?;
 
-    return TableMapping {
-        seeds,
-        seeds_to_soil: parse_next_table(chunks.next().unwrap()),
-        soil_to_fertilizer: parse_next_table(chunks.next().unwrap()),
-        fertilizer_to_water: parse_next_table(chunks.next().unwrap()),
-        water_to_light: parse_next_table(chunks.next().unwrap()),
-        light_to_temperature: parse_next_table(chunks.next().unwrap()),
-        temperature_to_humidity: parse_next_table(chunks.next().unwrap()),
-        humidity_to_location: parse_next_table(chunks.next().unwrap()),
+    let table = |name: &str| {
+        let section = sections
+            .require(&format!("{} map", name))
+            .map_err(|_| ParseError::new(format!("Missing the table \"{}\"", name)))?;
+        parse_table(section)
+            .map_err(|error| error.in_context(format_args!("In the table \"{}\"", name)))
     };
-    fn parse_next_table(lines: Vec<&str>) -> ImmutableSeq<RowMapping> {
-        let lines = lines.into_iter().skip(1);
-        let mut to_return: Vec<RowMapping> = lines
-            .map(|line| {
-                let mut numbers = line
-                    .split_whitespace()
-                    .map(|number| number.parse::<NumericVal>().unwrap());
-                let (dest_start, source_start, _range_len) = (
-                    numbers.next().unwrap(),
-                    numbers.next().unwrap(),
-                    numbers.next().unwrap(),
-                );
-                RowMapping::new(dest_start, source_start, _range_len)
+    return Ok(TableMapping {
+        seeds: seeds.into(),
+        seeds_to_soil: table("seed-to-soil")?,
+        soil_to_fertilizer: table("soil-to-fertilizer")?,
+        fertilizer_to_water: table("fertilizer-to-water")?,
+        water_to_light: table("water-to-light")?,
+        light_to_temperature: table("light-to-temperature")?,
+        temperature_to_humidity: table("temperature-to-humidity")?,
+        humidity_to_location: table("humidity-to-location")?,
+    });
+    fn parse_table(section: &Section) -> Result<ImmutableSeq<RowMapping>, ParseError> {
+        let (line_number, header) = section.header;
+        let trailing = section.inline.trim_start();
+        if !trailing.trim_end().is_empty() {
+            let start =
+                inline_offset(section) + section.inline.chars().count() - trailing.chars().count();
+            return Err(ParseError::new(format!(
+                "Expected nothing after \"map:\", found \"{}\"",
+                trailing.trim_end()
+            ))
+            .at_line(line_number)
+            .in_text(header, start..start + trailing.trim_end().chars().count()));
+        }
+        let row = try_map(
+            many(padded(unsigned::<NumericVal>)),
+            |numbers| match numbers[..] {
+                [dest_start, source_start, range_len] if range_len > 0 => {
+                    RowMapping::new(dest_start, source_start, range_len)
+                        .ok_or_else(|| format!("ranges which end at most at {}", NumericVal::MAX))
+                }
+                _ => Err("\"<destination> <source> <length>\" with a length above 0".to_string()),
+            },
+        );
+        let mut to_return: Vec<RowMapping> = section
+            .lines
+            .iter()
+            .map(|&(line_number, line)| {
+                parse_complete(&row, line).map_err(|error| error.at_line(line_number))
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
         to_return.sort_by_key(|key| (key.source_start, Reverse(key._range_len)));
-        ImmutableSeq::from(to_return)
+        Ok(ImmutableSeq::from(to_return))
+    }
+    fn reject_lines_below(section: &Section) -> Result<(), ParseError> {
+        match section.lines.first() {
+            Some(&(line_number, line)) => Err(ParseError::new(format!(
+                "Expected a blank line after the section \"{}\"",
+                section.name
+            ))
+            .at_line(line_number)
+            .in_text(line, 0..line.chars().count())),
+            None => Ok(()),
+        }
+    }
+    /// Column at which the text after the ':' of the header starts.
+    fn inline_offset(section: &Section) -> usize {
+        section.header.1.chars().count() - section.inline.chars().count()
     }
 }
 
@@ -241,16 +354,22 @@ pub struct RowMapping {
     source_end: NumericVal,
 }
 impl RowMapping {
-    fn new(dest_start: NumericVal, source_start: NumericVal, _range_len: NumericVal) -> Self {
-        let _dest_end = (dest_start + _range_len) - 1;
-        let source_end = (source_start + _range_len) - 1;
-        RowMapping {
+    /// Returns none for an empty range or if the end of either range does not fit into a number.
+    fn new(
+        dest_start: NumericVal,
+        source_start: NumericVal,
+        _range_len: NumericVal,
+    ) -> Option<Self> {
+        let last_offset = _range_len.checked_sub(1)?;
+        let _dest_end = dest_start.checked_add(last_offset)?;
+        let source_end = source_start.checked_add(last_offset)?;
+        Some(RowMapping {
             dest_start,
             source_start,
             _range_len,
             _dest_end,
             source_end,
-        }
+        })
     }
 
     fn create_source_range(&self) -> ItemRange {
@@ -278,9 +397,82 @@ mod testing {
 
     use super::*;
 
+    #[test]
+    fn day_5_parse_reordered_and_extra_sections() {
+        let mut blocks: Vec<&str> = EXAMPLE.split("\n\n").collect();
+        blocks[1..].reverse();
+        blocks.insert(3, "note:\nnot a table");
+        let reordered = blocks.join("\n\n");
+        let expected = Day5::part_one(&parsing(EXAMPLE).unwrap()).unwrap();
+        let actual = Day5::part_one(&parsing(&reordered).unwrap()).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn day_5_reject_text_outside_numbers() {
+        assert_case(
+            EXAMPLE.replace("seed-to-soil map:", "seed-to-soil map: 1 2"),
+            "Could not parse input at line 3, column 19: In the table \"seed-to-soil\": Expected nothing after \"map:\", found \"1 2\"",
+        );
+        assert_case(
+            EXAMPLE.replace("seeds: 79 14 55 13", "seeds: 79 14\n55 13"),
+            "Could not parse input at line 2, column 1: Expected a blank line after the section \"seeds\"",
+        );
+        assert_case(
+            EXAMPLE.replace("seeds: 79", "seeds: 99999999999999999999"),
+            "Could not parse input at line 1, column 8: Number 99999999999999999999 does not fit into u64",
+        );
+        fn assert_case(input: String, expected: &str) {
+            let actual = parsing(&input).unwrap_err().to_string();
+            assert_eq!(expected, actual, "Input: {}", input);
+        }
+    }
+
+    #[test]
+    fn day_5_read_seeds_as_ranges() {
+        assert_eq!(
+            Ok(vec![
+                ItemRange { start: 79, end: 92 },
+                ItemRange {
+                    start: NumericVal::MAX,
+                    end: NumericVal::MAX
+                },
+            ]),
+            seed_ranges(&[79, 14, NumericVal::MAX, 1]).map_err(|error| error.to_string())
+        );
+        assert_case(&[], "Invalid input: No seeds given");
+        assert_case(
+            &[79, 14, 55],
+            "Invalid input: Expected seeds as pairs of \"<start> <length>\", found no length after 55",
+        );
+        assert_case(
+            &[79, 14, 0, 0],
+            "Invalid input: Expected seed ranges with a length above 0, found the length 0 after 0",
+        );
+        assert_case(
+            &[NumericVal::MAX, 2],
+            "Invalid input: The seed range of 2 seeds starting at 18446744073709551615 ends above 18446744073709551615",
+        );
+        fn assert_case(seeds: &[NumericVal], expected: &str) {
+            let actual = seed_ranges(seeds).unwrap_err().to_string();
+            assert_eq!(expected, actual, "Seeds: {:?}", seeds);
+        }
+    }
+
+    #[test]
+    fn day_5_report_missing_and_duplicate_tables() {
+        let missing = EXAMPLE.replace("water-to-light", "water-to-lamp");
+        assert_eq!(
+            "Could not parse input: Missing the table \"water-to-light\"",
+            parsing(&missing).unwrap_err().to_string()
+        );
+        let duplicate = format!("{}\n\nseed-to-soil map:\n1 2 3", EXAMPLE);
+        assert_eq!(Some(35), parsing(&duplicate).unwrap_err().line());
+    }
+
     #[test]
     fn day_5_get_opt_range_from_row() {
-        let row = RowMapping::new(52, 50, 48);
+        let row = RowMapping::new(52, 50, 48).unwrap();
         assert_case(79, &row, Some(81));
         assert_case(100, &row, None);
         assert_case(49, &row, None);
@@ -294,9 +486,9 @@ mod testing {
     }
     #[test]
     fn day_5_get_mapping() {
-        let mapping: ImmutableSeq<RowMapping> = Rc::from(vec![
-            RowMapping::new(50, 98, 2),
-            RowMapping::new(52, 50, 48),
+        let mapping: ImmutableSeq<RowMapping> = Rc::from([
+            RowMapping::new(50, 98, 2).unwrap(),
+            RowMapping::new(52, 50, 48).unwrap(),
         ]);
         assert_case(13, mapping.clone(), 13);
         assert_case(79, mapping.clone(), 81);
@@ -389,12 +581,12 @@ mod testing {
     fn day_5_get_mapped_range_from() {
         assert_case(
             ItemRange { start: 98, end: 99 },
-            &RowMapping::new(50, 98, 2),
+            &RowMapping::new(50, 98, 2).unwrap(),
             Some(ItemRange { start: 50, end: 51 }),
         );
         assert_case(
             ItemRange { start: 50, end: 74 },
-            &RowMapping::new(52, 50, 48),
+            &RowMapping::new(52, 50, 48).unwrap(),
             Some(ItemRange { start: 52, end: 76 }),
         );
         assert_case(
@@ -402,17 +594,17 @@ mod testing {
                 start: 95,
                 end: 100,
             },
-            &RowMapping::new(52, 50, 48),
+            &RowMapping::new(52, 50, 48).unwrap(),
             Some(ItemRange { start: 97, end: 99 }),
         );
         assert_case(
             ItemRange { start: 60, end: 79 },
-            &RowMapping::new(60, 50, 30),
+            &RowMapping::new(60, 50, 30).unwrap(),
             Some(ItemRange { start: 70, end: 89 }),
         );
         assert_case(
             ItemRange { start: 30, end: 45 },
-            &RowMapping::new(18, 25, 70),
+            &RowMapping::new(18, 25, 70).unwrap(),
             Some(ItemRange { start: 23, end: 38 }),
         );
         fn assert_case(base: ItemRange, row: &RowMapping, expected: Option<ItemRange>) {
@@ -420,4 +612,13 @@ mod testing {
             assert_eq!(expected, actual, "Base: {:?}\nRow: {:?}", base, row);
         }
     }
+
+    #[test]
+    fn day_5_report_row_ending_above_max() {
+        let input = EXAMPLE.replace("50 98 2", "18446744073709551615 0 5");
+        assert_eq!(
+            "Could not parse input at line 4, column 1: In the table \"seed-to-soil\": Expected ranges which end at most at 18446744073709551615, found \"18446744073709551615 0 5\"",
+            parsing(&input).unwrap_err().to_string()
+        );
+    }
 }
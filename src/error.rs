@@ -0,0 +1,241 @@
+use std::{io, ops::Range, path::Path, process::ExitCode};
+
+use derive_more::{Display, From};
+
+use crate::input_discovery::MissingInput;
+
+pub type AocResult<T> = Result<T, AocError>;
+
+#[derive(Debug, Display, From)]
+pub enum AocError {
+    #[display(fmt = "{}", _0)]
+    #[from]
+    Parse(ParseError),
+    #[display(fmt = "Invalid input: {}", _0)]
+    InvalidInput(String),
+    #[display(
+        fmt = "No function registered to handle task {} under day {}",
+        task,
+        day
+    )]
+    UnsupportedTask { day: usize, task: usize },
+    #[display(fmt = "{}: {}", context, error)]
+    Io { context: String, error: io::Error },
+    #[display(fmt = "{}", _0)]
+    #[from]
+    MissingInput(MissingInput),
+}
+
+impl std::error::Error for AocError {}
+
+impl AocError {
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::InvalidInput(message.into())
+    }
+
+    pub fn io(context: impl Into<String>, error: io::Error) -> Self {
+        Self::Io {
+            context: context.into(),
+            error,
+        }
+    }
+
+    pub fn reading_file(path: &Path, error: io::Error) -> Self {
+        Self::io(format!("Could not read file {}", path.display()), error)
+    }
+
+    /// Short stable name of the variant for machine readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AocError::Parse(_) => "parse",
+            AocError::InvalidInput(_) => "invalid_input",
+            AocError::UnsupportedTask { .. } => "unsupported_task",
+            AocError::Io { .. } => "io",
+            AocError::MissingInput(_) => "missing_input",
+        }
+    }
+
+    /// Like the display output but parse errors show the offending line with a caret underline.
+    pub fn diagnostic(&self) -> String {
+        match self {
+            AocError::Parse(error) => error.diagnostic(),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Exit code 1 is left for failed verifications or slowdowns which are not errors.
+    /// Exit code 2 is used by clap for invalid command line arguments.
+    pub fn exit_code(&self) -> ExitCode {
+        let code = match self {
+            AocError::Parse(_) => 3,
+            AocError::InvalidInput(_) => 4,
+            AocError::UnsupportedTask { .. } => 5,
+            AocError::Io { .. } | AocError::MissingInput(_) => 6,
+        };
+        ExitCode::from(code)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    line: Option<usize>,
+    snippet: Option<Snippet>,
+}
+
+/// Text of the offending line with the columns to underline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Snippet {
+    text: String,
+    columns: Range<usize>,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            snippet: None,
+        }
+    }
+
+    /// Line numbers start at 1.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Text of the line the error is about with the offending columns.
+    /// Columns count characters from 0, the end is exclusive.
+    /// An empty range points right before its start, for example past the end of the line.
+    pub fn in_text(mut self, text: &str, columns: Range<usize>) -> Self {
+        self.snippet = Some(Snippet {
+            text: text.to_string(),
+            columns,
+        });
+        self
+    }
+
+    /// Moves the snippet of an error about a part of a line onto the whole line.
+    /// The part starts at the given column of the line.
+    pub fn within_line(mut self, line: &str, offset: usize) -> Self {
+        if let Some(snippet) = &mut self.snippet {
+            snippet.text = line.to_string();
+            snippet.columns = snippet.columns.start + offset..snippet.columns.end + offset;
+        }
+        self
+    }
+
+    /// Puts the context in front of the message like "In the table \"x\": <message>".
+    pub fn in_context(mut self, context: impl std::fmt::Display) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Column of the first offending character, starting at 1.
+    pub fn column(&self) -> Option<usize> {
+        self.snippet
+            .as_ref()
+            .map(|snippet| snippet.columns.start + 1)
+    }
+
+    /// Characters under the underlined columns.
+    pub fn offending_text(&self) -> Option<String> {
+        self.snippet.as_ref().map(|snippet| {
+            snippet
+                .text
+                .chars()
+                .skip(snippet.columns.start)
+                .take(snippet.columns.len())
+                .collect()
+        })
+    }
+
+    /// Message followed by the offending line with a caret underline, like a compiler diagnostic.
+    /// Same as the display output if the line text is unknown.
+    pub fn diagnostic(&self) -> String {
+        let Some(snippet) = &self.snippet else {
+            return self.to_string();
+        };
+        let label = self.line.map(|line| line.to_string()).unwrap_or_default();
+        let gutter = " ".repeat(label.len());
+        format!(
+            "{}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            label,
+            snippet.text,
+            gutter,
+            " ".repeat(snippet.columns.start),
+            "^".repeat(snippet.columns.len().max(1))
+        )
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Could not parse input")?;
+        match (self.line, self.column()) {
+            (Some(line), Some(column)) => write!(f, " at line {}, column {}", line, column)?,
+            (Some(line), None) => write!(f, " at line {}", line)?,
+            (None, Some(column)) => write!(f, " at column {}", column)?,
+            (None, None) => (),
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn underline_offending_columns() {
+        assert_case(
+            ParseError::new("Expected a number, found \"x\"")
+                .at_line(12)
+                .in_text("Game 1: x blue", 8..9),
+            "Could not parse input at line 12, column 9: Expected a number, found \"x\"
+   |
+12 | Game 1: x blue
+   |         ^",
+        );
+        assert_case(
+            ParseError::new("Expected \",\"").in_text("1 2", 3..3),
+            "Could not parse input at column 4: Expected \",\"
+ |
+ | 1 2
+ |    ^",
+        );
+        assert_case(
+            ParseError::new("Missing seeds").at_line(1),
+            "Could not parse input at line 1: Missing seeds",
+        );
+        fn assert_case(error: ParseError, expected: &str) {
+            assert_eq!(expected, error.diagnostic(), "Error: {:?}", error);
+        }
+    }
+
+    #[test]
+    fn move_snippet_onto_whole_line() {
+        let error = ParseError::new("Too big")
+            .in_text("1 300", 2..5)
+            .within_line("seeds: 1 300", 7);
+        assert_eq!(Some(10), error.column());
+        assert_eq!(Some("300".to_string()), error.offending_text());
+    }
+
+    #[test]
+    fn offending_text_of_span() {
+        let error = ParseError::new("Too long").in_text("äbcdef", 1..4);
+        assert_eq!(Some("bcd".to_string()), error.offending_text());
+        assert_eq!(Some(2), error.column());
+        assert_eq!(None, ParseError::new("Too long").offending_text());
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use derive_more::Display;
+
+use crate::error::{AocError, AocResult};
+
+pub const DEFAULT_INPUT_DIR: &str = "input";
+
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display(
+    fmt = "No input found for day {}. Tried: {}",
+    day,
+    "display_paths(tried)"
+)]
+pub struct MissingInput {
+    pub day: usize,
+    pub tried: Vec<PathBuf>,
+}
+
+impl std::error::Error for MissingInput {}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Conventional file name of a day's input: "day_<N>.txt" or "day_<N>_example.txt".
+pub fn input_file_name(day: usize, example: bool) -> String {
+    if example {
+        format!("day_{}_example.txt", day)
+    } else {
+        format!("day_{}.txt", day)
+    }
+}
+
+/// Paths looked up in order for the input of a day.
+/// A relative input directory is relative to the working directory.
+pub fn candidate_paths(input_dir: &Path, day: usize, example: bool) -> Vec<PathBuf> {
+    vec![input_dir.join(input_file_name(day, example))]
+}
+
+/// Returns the content of the first existing candidate path.
+/// Only paths which do not exist are skipped, any other failure to read one is an error.
+pub fn find_input(input_dir: &Path, day: usize, example: bool) -> AocResult<String> {
+    let tried = candidate_paths(input_dir, day, example);
+    for path in &tried {
+        match std::fs::read_to_string(path) {
+            Ok(input) => return Ok(input),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => (),
+            Err(error) => return Err(AocError::reading_file(path, error)),
+        }
+    }
+    Err(MissingInput { day, tried }.into())
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn candidate_paths_of_day() {
+        let actual = candidate_paths(Path::new("/tmp/inputs"), 3, false);
+        assert_eq!(vec![PathBuf::from("/tmp/inputs/day_3.txt")], actual);
+
+        let actual = candidate_paths(Path::new(DEFAULT_INPUT_DIR), 12, true);
+        assert_eq!(vec![PathBuf::from("input/day_12_example.txt")], actual);
+    }
+
+    #[test]
+    fn name_tried_paths_if_missing() {
+        let actual = find_input(Path::new("/not/existing"), 1, false).unwrap_err();
+        assert_eq!(
+            "No input found for day 1. Tried: /not/existing/day_1.txt",
+            actual.to_string()
+        );
+        assert!(matches!(actual, AocError::MissingInput(_)));
+    }
+
+    #[test]
+    fn report_unreadable_input() {
+        let input_dir = std::env::temp_dir().join("aoc_unreadable_input");
+        std::fs::create_dir_all(input_dir.join("day_1.txt")).unwrap();
+        let actual = find_input(&input_dir, 1, false).unwrap_err();
+        assert!(matches!(actual, AocError::Io { .. }));
+        assert!(
+            actual.to_string().starts_with(&format!(
+                "Could not read file {}",
+                input_dir.join("day_1.txt").display()
+            )),
+            "Error: {}",
+            actual
+        );
+    }
+}
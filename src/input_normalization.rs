@@ -0,0 +1,115 @@
+use derive_more::Display;
+
+/// One kind of change made to an input before the solvers see it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum Normalization {
+    #[display(fmt = "removed the UTF-8 byte order mark")]
+    RemovedBom,
+    #[display(fmt = "converted {} CRLF line endings to LF", _0)]
+    ConvertedCrlf(usize),
+    #[display(fmt = "removed {} trailing blank lines", _0)]
+    TrimmedTrailingBlankLines(usize),
+}
+
+/// Input with the changes made to it, empty if it was already normalized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedInput {
+    pub text: String,
+    pub changes: Vec<Normalization>,
+}
+
+impl NormalizedInput {
+    /// Line of the form "Warning: ..." listing every change, none if nothing changed.
+    pub fn warning(&self, day: usize) -> Option<String> {
+        if self.changes.is_empty() {
+            return None;
+        }
+        let changes: Vec<String> = self.changes.iter().map(ToString::to_string).collect();
+        Some(format!(
+            "Warning: Normalized the input of day {}: {}",
+            day,
+            changes.join(", ")
+        ))
+    }
+}
+
+/// Removes a byte order mark, converts CRLF to LF and drops whitespace-only lines at the end.
+/// The line break after the last line with content is kept.
+pub fn normalize(text: &str) -> NormalizedInput {
+    let mut changes = Vec::new();
+    let text = match text.strip_prefix('\u{feff}') {
+        Some(rest) => {
+            changes.push(Normalization::RemovedBom);
+            rest
+        }
+        None => text,
+    };
+    let crlf = text.matches("\r\n").count();
+    let mut text = if crlf > 0 {
+        changes.push(Normalization::ConvertedCrlf(crlf));
+        text.replace("\r\n", "\n")
+    } else {
+        text.to_string()
+    };
+    let content_end = text.trim_end().len();
+    let keep = if content_end == 0 {
+        0
+    } else {
+        text[content_end..]
+            .find('\n')
+            .map_or(text.len(), |newline| content_end + newline + 1)
+    };
+    let blank_lines = text[keep..].lines().count();
+    if blank_lines > 0 {
+        changes.push(Normalization::TrimmedTrailingBlankLines(blank_lines));
+        text.truncate(keep);
+    }
+    NormalizedInput { text, changes }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn normalize_inputs() {
+        assert_case("a\nb\n", "a\nb\n", &[]);
+        assert_case("a\nb", "a\nb", &[]);
+        assert_case("a  \nb  ", "a  \nb  ", &[]);
+        assert_case("", "", &[]);
+        assert_case(
+            "\u{feff}a\r\nb\r\n\r\n  \n",
+            "a\nb\n",
+            &[
+                Normalization::RemovedBom,
+                Normalization::ConvertedCrlf(3),
+                Normalization::TrimmedTrailingBlankLines(2),
+            ],
+        );
+        assert_case(
+            "a\n\n\t",
+            "a\n",
+            &[Normalization::TrimmedTrailingBlankLines(2)],
+        );
+        assert_case(" \n\n", "", &[Normalization::TrimmedTrailingBlankLines(2)]);
+        assert_case("a\n\nb\n", "a\n\nb\n", &[]);
+        fn assert_case(input: &str, expected: &str, changes: &[Normalization]) {
+            let actual = normalize(input);
+            assert_eq!(expected, actual.text, "Input: {:?}", input);
+            assert_eq!(changes, actual.changes, "Input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn warn_about_changes() {
+        assert_eq!(None, normalize("a\n").warning(1));
+        assert_eq!(
+            Some(
+                "Warning: Normalized the input of day 4: converted 1 CRLF line endings to LF, \
+                 removed 1 trailing blank lines"
+                    .to_string()
+            ),
+            normalize("a\r\n\n").warning(4)
+        );
+    }
+}
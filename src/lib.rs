@@ -0,0 +1,14 @@
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod days;
+pub mod error;
+pub mod input_discovery;
+pub mod input_normalization;
+pub mod prelude;
+pub mod report;
+pub mod run_all;
+pub mod solver;
+pub mod table;
+pub mod task_handlers;
+pub mod utils;
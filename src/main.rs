@@ -1,55 +1,195 @@
-use std::{collections::HashMap, process::ExitCode};
+use std::{path::Path, process::ExitCode};
 
+use advent_of_code_2023::answers::{ExpectedAnswers, Verdict};
+use advent_of_code_2023::bench::{self, Baseline, Comparison, CountingAllocator};
+use advent_of_code_2023::cli::{AppCli, AppCommand, BenchCli, RunAllCli, TaskOverCli};
+use advent_of_code_2023::error::AocResult;
+use advent_of_code_2023::input_normalization;
+use advent_of_code_2023::report::{self, OutputFormat, TaskReport};
+use advent_of_code_2023::run_all::{self, RunOutcome, TaskRun};
+use advent_of_code_2023::solver::Annotator;
+use advent_of_code_2023::task_handlers::{self, TaskHandlers};
+use advent_of_code_2023::utils::render::RenderStyle;
 use clap::Parser;
-use days::day_1;
-use days::day_2;
-use days::day_3;
-use days::day_4;
-use days::day_5;
-
-pub mod prelude;
-mod utils;
-use crate::cli::TaskOverCli;
-mod days;
-
-mod cli;
-type TaskHandlers = HashMap<(usize, usize), Handler>;
-type Handler = fn(String) -> String;
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
 fn main() -> ExitCode {
-    let args = TaskOverCli::parse();
-    let mut tasks_handlers: TaskHandlers = Default::default();
-    register_handler(&mut tasks_handlers, 1, 1, day_1::handle);
-    register_handler(&mut tasks_handlers, 1, 2, day_1::handle_task_2);
-    register_handler(&mut tasks_handlers, 2, 1, day_2::handle_task);
-    register_handler(&mut tasks_handlers, 2, 2, day_2::handle_task_2);
-    register_handler(&mut tasks_handlers, 3, 1, day_3::handle_task);
-    register_handler(&mut tasks_handlers, 3, 2, day_3::handle_task_2);
-    register_handler(&mut tasks_handlers, 4, 1, day_4::handle_task);
-    register_handler(&mut tasks_handlers, 4, 2, day_4::handle_task_2);
-    register_handler(&mut tasks_handlers, 5, 1, day_5::handle_task);
-    register_handler(&mut tasks_handlers, 5, 2, day_5::handle_task_2);
-    register_handler(&mut tasks_handlers, 5, 3, day_5::handle_task_3);
-
-    match tasks_handlers.get(&(args.day, args.task)) {
-        Some(handler) => {
-            let result = handler(args.input.content);
-            println!("{}", result);
-            ExitCode::SUCCESS
-        }
-        None => {
-            eprintln!(
-                "No function registered to handle task {} under day {}",
-                args.task, args.day
-            );
-            ExitCode::FAILURE
+    let args = AppCli::parse();
+    let tasks_handlers = task_handlers::all_task_handlers();
+    let result = match args.command {
+        AppCommand::Run(args) => run_task(&tasks_handlers, args),
+        AppCommand::All(args) => run_all_tasks(&tasks_handlers, args),
+        AppCommand::Bench(args) => bench_task(&tasks_handlers, args),
+        AppCommand::List => {
+            print!("{}", task_handlers::render_list(&tasks_handlers));
+            Ok(ExitCode::SUCCESS)
         }
+    };
+    result.unwrap_or_else(|error| {
+        eprintln!("{}", error.diagnostic());
+        error.exit_code()
+    })
+}
+
+fn run_task(tasks_handlers: &TaskHandlers, args: TaskOverCli) -> AocResult<ExitCode> {
+    let (day, task) = (args.day, args.task);
+    let raw_input = args.input.raw_input;
+    let answers = if args.input.example {
+        Some(task_handlers::example_answers(tasks_handlers))
+    } else {
+        load_answers(args.answers.as_deref())?
+    };
+    let run =
+        match task_handlers::registered_task(tasks_handlers, day, task).and_then(|registered| {
+            let example = registered.example.map(|(input, _)| input);
+            let input = args.input.resolve(day, example)?;
+            Ok((registered, prepare_input(day, input, raw_input)))
+        }) {
+            Ok((registered, input)) => {
+                if args.dump_grid {
+                    dump_grid(day, registered.annotator, &input);
+                }
+                TaskRun::execute(day, task, &registered.handler, &input)
+            }
+            Err(error) => TaskRun::failed(day, task, error),
+        };
+    let verdict = answers.as_ref().and_then(|answers| run.verdict(answers));
+
+    match args.format {
+        OutputFormat::Text => match &run.outcome {
+            RunOutcome::Solved(result) => {
+                println!("{}", result);
+                match verdict
+                    .as_ref()
+                    .map(|verdict| (verdict, verdict.expected()))
+                {
+                    Some((verdict, Some(expected))) => {
+                        println!("{}: expected {}, actual {}", verdict, expected, result)
+                    }
+                    Some((verdict, None)) => println!("{}", verdict),
+                    None => (),
+                }
+            }
+            RunOutcome::Failed(error) => eprintln!("{}", error.diagnostic()),
+            RunOutcome::MissingInput(missing) => eprintln!("{}", missing),
+        },
+        OutputFormat::Json => print!(
+            "{}",
+            report::render_json(&TaskReport::new(&run, answers.as_ref()))
+        ),
+        OutputFormat::Jsonl => print!(
+            "{}",
+            report::render_json_lines(&[TaskReport::new(&run, answers.as_ref())])
+        ),
     }
+
+    Ok(match run.outcome {
+        RunOutcome::Failed(error) => error.exit_code(),
+        _ if matches!(verdict, Some(Verdict::Fail { .. })) => ExitCode::FAILURE,
+        _ => ExitCode::SUCCESS,
+    })
 }
-fn register_handler(handlers: &mut TaskHandlers, day: usize, task: usize, handler: Handler) {
-    assert!(
-        handlers.insert((day, task), handler).is_none(),
-        "Registered more than one task hanlder to task {} under the day {}",
-        task,
-        day
+
+fn run_all_tasks(tasks_handlers: &TaskHandlers, args: RunAllCli) -> AocResult<ExitCode> {
+    let answers = if args.example {
+        Some(task_handlers::example_answers(tasks_handlers))
+    } else {
+        load_answers(args.answers.as_deref())?
+    };
+    let runs = run_all::run_all(
+        tasks_handlers,
+        &args.input_dir,
+        args.example,
+        |day, input| prepare_input(day, input, args.raw_input),
     );
+    let reports = || -> Vec<TaskReport> {
+        runs.iter()
+            .map(|run| TaskReport::new(run, answers.as_ref()))
+            .collect()
+    };
+    match args.format {
+        OutputFormat::Text => print!("{}", run_all::render_table(&runs, answers.as_ref())),
+        OutputFormat::Json => print!("{}", report::render_json(&reports())),
+        OutputFormat::Jsonl => print!("{}", report::render_json_lines(&reports())),
+    }
+    let any_mismatch = answers.is_some_and(|answers| {
+        runs.iter()
+            .any(|run| matches!(run.verdict(&answers), Some(Verdict::Fail { .. })))
+    });
+    if any_mismatch || runs.iter().any(|run| run.has_failed()) {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn bench_task(tasks_handlers: &TaskHandlers, args: BenchCli) -> AocResult<ExitCode> {
+    let registered = task_handlers::registered_task(tasks_handlers, args.day, args.task)?;
+    let example = registered.example.map(|(input, _)| input);
+    let raw_input = args.input.raw_input;
+    let input = prepare_input(args.day, args.input.resolve(args.day, example)?, raw_input);
+    let report = bench::bench(
+        &registered.handler,
+        &input,
+        (args.day, args.task),
+        args.iterations as usize,
+    )?;
+    print!("{}", report.render());
+
+    let mut exit_code = ExitCode::SUCCESS;
+    if let Some(path) = &args.baseline {
+        match Baseline::from_file(path)?.get(args.day, args.task) {
+            Some(entry) => {
+                let comparison = Comparison::new(entry, &report);
+                let slower = comparison.is_slowdown(args.threshold);
+                println!(
+                    "Baseline median {:?}, now {:?} ({:+.1}%){}",
+                    comparison.baseline,
+                    comparison.current,
+                    comparison.change_percent(),
+                    if slower {
+                        format!(", SLOWDOWN beyond {}%", args.threshold)
+                    } else {
+                        String::new()
+                    }
+                );
+                if slower {
+                    exit_code = ExitCode::FAILURE;
+                }
+            }
+            None => println!("No baseline entry for this task in {}", path.display()),
+        }
+    }
+    if let Some(path) = &args.save_baseline {
+        let mut baseline = Baseline::from_file(path)?;
+        baseline.record(&report);
+        baseline.save(path)?;
+    }
+    Ok(exit_code)
+}
+
+/// Normalizes the input unless it should stay raw and warns on stderr about every change.
+fn prepare_input(day: usize, input: String, raw: bool) -> String {
+    if raw {
+        return input;
+    }
+    let normalized = input_normalization::normalize(&input);
+    if let Some(warning) = normalized.warning(day) {
+        eprintln!("{}", warning);
+    }
+    normalized.text
+}
+
+fn dump_grid(day: usize, annotator: Annotator, input: &str) {
+    match annotator(input, RenderStyle::for_stdout()) {
+        Ok(Some(grid)) => print!("{}", grid),
+        Ok(None) => eprintln!("Day {} has no grid to dump", day),
+        Err(error) => eprintln!("Could not dump the grid of day {}: {}", day, error),
+    }
+}
+
+fn load_answers(path: Option<&Path>) -> AocResult<Option<ExpectedAnswers>> {
+    path.map(ExpectedAnswers::from_file).transpose()
 }
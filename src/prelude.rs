@@ -1,3 +1,8 @@
 pub use crate::utils::grid_2d;
+pub use crate::utils::grid_2d::Direction;
+pub use crate::utils::render::RenderStyle;
 pub use crate::utils::Coords;
+pub use crate::utils::Grid;
+pub use crate::utils::Point;
+pub use crate::utils::SparseGrid;
 pub use array2d::Array2D;
@@ -0,0 +1,144 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    answers::ExpectedAnswers,
+    run_all::{RunOutcome, TaskRun},
+    solver::Answer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable text
+    #[default]
+    Text,
+    /// One JSON object for a single task or a JSON array for several tasks
+    Json,
+    /// One JSON object per line and task
+    Jsonl,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ErrorReport {
+    pub kind: &'static str,
+    pub message: String,
+}
+
+/// Machine readable outcome of one task.
+/// Unsigned answers are JSON numbers, text answers are JSON strings.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TaskReport {
+    pub day: usize,
+    pub part: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer_type: Option<&'static str>,
+    pub parse_duration_ns: u64,
+    pub solve_duration_ns: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorReport>,
+}
+
+impl TaskReport {
+    /// Expected answer and verdict are only filled in if answers are given.
+    pub fn new(run: &TaskRun, answers: Option<&ExpectedAnswers>) -> Self {
+        let (answer, error) = match &run.outcome {
+            RunOutcome::Solved(answer) => (Some(answer), None),
+            RunOutcome::Failed(error) => (
+                None,
+                Some(ErrorReport {
+                    kind: error.kind(),
+                    message: error.to_string(),
+                }),
+            ),
+            RunOutcome::MissingInput(missing) => (
+                None,
+                Some(ErrorReport {
+                    kind: "missing_input",
+                    message: missing.to_string(),
+                }),
+            ),
+        };
+        Self {
+            day: run.day,
+            part: run.task,
+            answer: answer.map(|answer| match answer {
+                Answer::Unsigned(number) => Value::from(*number),
+                Answer::Text(text) => Value::from(text.as_str()),
+            }),
+            answer_type: answer.map(Answer::type_name),
+            parse_duration_ns: run.timings.parse.as_nanos() as u64,
+            solve_duration_ns: run.timings.solve.as_nanos() as u64,
+            expected: answers
+                .and_then(|answers| answers.expected(run.day, run.task))
+                .map(String::from),
+            verdict: answers
+                .and_then(|answers| run.verdict(answers))
+                .map(|verdict| verdict.to_string()),
+            error,
+        }
+    }
+}
+
+pub fn render_json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap() + "\n"
+}
+
+pub fn render_json_lines(reports: &[TaskReport]) -> String {
+    reports
+        .iter()
+        .map(|report| serde_json::to_string(report).unwrap() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod testing {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::*;
+    use crate::{error::AocError, input_discovery::MissingInput, solver::StageTimings};
+
+    #[test]
+    fn json_lines_of_runs() {
+        let runs = [
+            TaskRun {
+                day: 1,
+                task: 1,
+                outcome: RunOutcome::Solved(Answer::Unsigned(142)),
+                timings: StageTimings {
+                    parse: Duration::from_nanos(1500),
+                    solve: Duration::from_nanos(300),
+                },
+            },
+            TaskRun {
+                day: 1,
+                task: 2,
+                outcome: RunOutcome::Solved(Answer::Text("abc".to_string())),
+                timings: StageTimings::default(),
+            },
+            TaskRun::failed(2, 1, AocError::invalid_input("No games")),
+            TaskRun {
+                day: 3,
+                task: 1,
+                outcome: RunOutcome::MissingInput(MissingInput {
+                    day: 3,
+                    tried: vec![PathBuf::from("input/day_3.txt")],
+                }),
+                timings: StageTimings::default(),
+            },
+        ];
+        let answers = ExpectedAnswers::parse("1 1 142\n1 2 xyz").unwrap();
+        let reports: Vec<TaskReport> = runs
+            .iter()
+            .map(|run| TaskReport::new(run, Some(&answers)))
+            .collect();
+        let actual = render_json_lines(&reports);
+        insta::assert_snapshot!(actual);
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::Path,
+};
+
+use crate::{
+    answers::{ExpectedAnswers, Verdict},
+    error::AocError,
+    input_discovery::{self, MissingInput},
+    solver::{Answer, Handler, StageTimings},
+    table,
+    task_handlers::TaskHandlers,
+};
+
+#[derive(Debug)]
+pub enum RunOutcome {
+    Solved(Answer),
+    Failed(AocError),
+    MissingInput(MissingInput),
+}
+
+#[derive(Debug)]
+pub struct TaskRun {
+    pub day: usize,
+    pub task: usize,
+    pub outcome: RunOutcome,
+    pub timings: StageTimings,
+}
+
+impl TaskRun {
+    pub fn execute(day: usize, task: usize, handler: &Handler, input: &str) -> Self {
+        match handler(input) {
+            Ok(solved) => Self {
+                day,
+                task,
+                outcome: RunOutcome::Solved(solved.answer),
+                timings: solved.timings,
+            },
+            Err(error) => Self::failed(day, task, error),
+        }
+    }
+
+    pub fn failed(day: usize, task: usize, error: AocError) -> Self {
+        Self {
+            day,
+            task,
+            outcome: RunOutcome::Failed(error),
+            timings: StageTimings::default(),
+        }
+    }
+
+    pub fn has_failed(&self) -> bool {
+        matches!(self.outcome, RunOutcome::Failed(_))
+    }
+
+    /// Returns none if the task could not produce an answer to compare.
+    pub fn verdict(&self, answers: &ExpectedAnswers) -> Option<Verdict> {
+        match &self.outcome {
+            RunOutcome::Solved(answer) => Some(answers.verify(self.day, self.task, answer)),
+            RunOutcome::Failed(_) | RunOutcome::MissingInput(_) => None,
+        }
+    }
+}
+
+/// Runs every registered task with the input of its day found under `input_dir`.
+/// Every input file is only read once, even if the day has several tasks.
+/// A missing or unreadable input is reported for every task of its day.
+/// With `example`, tasks with an embedded example run on it instead.
+/// Every input read from a file goes through `prepare_input` together with its day first.
+pub fn run_all(
+    handlers: &TaskHandlers,
+    input_dir: &Path,
+    example: bool,
+    prepare_input: impl Fn(usize, String) -> String,
+) -> Vec<TaskRun> {
+    let mut inputs: HashMap<usize, String> = HashMap::new();
+    handlers
+        .iter()
+        .map(|(&(day, task), registered)| {
+            if let (true, Some((input, _))) = (example, registered.example) {
+                return TaskRun::execute(day, task, &registered.handler, input);
+            }
+            let input = match inputs.entry(day) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    match input_discovery::find_input(input_dir, day, example) {
+                        Ok(input) => entry.insert(prepare_input(day, input)),
+                        Err(AocError::MissingInput(missing)) => {
+                            return TaskRun {
+                                day,
+                                task,
+                                outcome: RunOutcome::MissingInput(missing),
+                                timings: StageTimings::default(),
+                            }
+                        }
+                        Err(error) => return TaskRun::failed(day, task, error),
+                    }
+                }
+            };
+            TaskRun::execute(day, task, &registered.handler, input)
+        })
+        .collect()
+}
+
+/// Adds the columns "Expected" and "Verdict" if answers are given.
+pub fn render_table(runs: &[TaskRun], answers: Option<&ExpectedAnswers>) -> String {
+    let mut header = vec!["Day", "Part", "Answer", "Duration"];
+    if answers.is_some() {
+        header.extend(["Expected", "Verdict"]);
+    }
+    let rows: Vec<Vec<String>> = runs
+        .iter()
+        .map(|run| {
+            let (answer, duration) = match &run.outcome {
+                RunOutcome::Solved(answer) => {
+                    (answer.to_string(), format!("{:?}", run.timings.total()))
+                }
+                RunOutcome::Failed(error) => {
+                    (error.to_string(), format!("{:?}", run.timings.total()))
+                }
+                RunOutcome::MissingInput(missing) => (missing.to_string(), "-".to_string()),
+            };
+            let mut row = vec![run.day.to_string(), run.task.to_string(), answer, duration];
+            if let Some(answers) = answers {
+                let expected = answers.expected(run.day, run.task).unwrap_or("-");
+                let verdict = run
+                    .verdict(answers)
+                    .map(|verdict| verdict.to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                row.extend([expected.to_string(), verdict]);
+            }
+            row
+        })
+        .collect();
+
+    table::render_table(&header, &rows)
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use std::{path::PathBuf, time::Duration};
+
+    fn runs() -> Vec<TaskRun> {
+        vec![
+            TaskRun {
+                day: 1,
+                task: 1,
+                outcome: RunOutcome::Solved(Answer::Unsigned(142)),
+                timings: StageTimings {
+                    parse: Duration::from_millis(1),
+                    solve: Duration::from_millis(1),
+                },
+            },
+            TaskRun {
+                day: 1,
+                task: 2,
+                outcome: RunOutcome::Solved(Answer::Text("abc".to_string())),
+                timings: StageTimings {
+                    parse: Duration::from_micros(5),
+                    solve: Duration::from_micros(15),
+                },
+            },
+            TaskRun {
+                day: 12,
+                task: 1,
+                outcome: RunOutcome::MissingInput(MissingInput {
+                    day: 12,
+                    tried: vec![PathBuf::from("input/day_12.txt")],
+                }),
+                timings: StageTimings::default(),
+            },
+        ]
+    }
+
+    #[test]
+    fn render_table_of_runs() {
+        let actual = render_table(&runs(), None);
+        insta::assert_snapshot!(actual);
+    }
+
+    #[test]
+    fn render_table_of_runs_with_verdicts() {
+        let answers = ExpectedAnswers::parse("1 1 142\n1 2 xyz").unwrap();
+        let actual = render_table(&runs(), Some(&answers));
+        insta::assert_snapshot!(actual);
+    }
+}
@@ -0,0 +1,198 @@
+use std::time::{Duration, Instant};
+
+use derive_more::Display;
+
+use crate::error::AocResult;
+use crate::utils::render::RenderStyle;
+
+/// Result of solving one part of a day.
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum Answer {
+    Unsigned(u64),
+    Text(String),
+}
+
+impl Answer {
+    /// Short stable name of the variant for machine readable output.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Text(_) => "text",
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Self::Unsigned(value.into())
+    }
+}
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Self::Unsigned(value)
+    }
+}
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(value as u64)
+    }
+}
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+pub type PartSolver<P> = fn(&P) -> AocResult<Answer>;
+
+/// Official example of a puzzle with the expected answers of the parts it is given for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    /// Pairs of part number, starting at 1, and expected answer.
+    pub answers: &'static [(usize, &'static str)],
+}
+
+impl Example {
+    /// Returns the input of the first example with an answer for the given part,
+    /// together with that answer.
+    pub fn for_part(examples: &[Example], part: usize) -> Option<(&'static str, &'static str)> {
+        examples.iter().find_map(|example| {
+            example
+                .answers
+                .iter()
+                .find(|(answer_part, _)| *answer_part == part)
+                .map(|(_, expected)| (example.input, *expected))
+        })
+    }
+}
+
+/// A day parses its input once into [`Solver::Parsed`] which is then shared by all its parts.
+pub trait Solver {
+    type Parsed;
+
+    /// Title of the puzzle, shown when listing the available tasks.
+    const TITLE: &'static str;
+    /// Checked by the unit tests generated for every day and runnable via the flag "--example".
+    const EXAMPLES: &'static [Example] = &[];
+
+    fn parse(input: &str) -> AocResult<Self::Parsed>;
+    fn part_one(parsed: &Self::Parsed) -> AocResult<Answer>;
+    fn part_two(parsed: &Self::Parsed) -> AocResult<Answer>;
+
+    /// Alternative solutions next to the 2 official parts, paired with a short title.
+    /// They are numbered as parts from 3 onwards.
+    fn additional_parts() -> &'static [(&'static str, PartSolver<Self::Parsed>)] {
+        &[]
+    }
+
+    /// Grid of the parsed input with the cells relevant to the answers highlighted.
+    /// Printed by the flag "--dump-grid" for debugging. Days without a grid return none.
+    fn render_annotated(_parsed: &Self::Parsed, _style: RenderStyle) -> Option<String> {
+        None
+    }
+}
+
+/// Parses the raw puzzle input and renders the annotated grid of a day, if it has one.
+pub type Annotator = fn(&str, RenderStyle) -> AocResult<Option<String>>;
+
+pub fn annotator_of<S: Solver>(input: &str, style: RenderStyle) -> AocResult<Option<String>> {
+    let parsed = S::parse(input)?;
+    Ok(S::render_annotated(&parsed, style))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StageTimings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl StageTimings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+    pub answer: Answer,
+    pub timings: StageTimings,
+}
+
+/// Solves one part from the raw puzzle input.
+/// Hides the parsed type of the solver so handlers of different days can be stored together.
+pub type Handler = Box<dyn Fn(&str) -> AocResult<Solved>>;
+
+pub fn handler_of<S>(part: PartSolver<S::Parsed>) -> Handler
+where
+    S: Solver + 'static,
+{
+    Box::new(move |input| {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let answer = part(&parsed)?;
+        let solve = start.elapsed();
+
+        Ok(Solved {
+            answer,
+            timings: StageTimings { parse, solve },
+        })
+    })
+}
+
+pub struct SolverPart {
+    pub task: usize,
+    pub title: &'static str,
+    pub handler: Handler,
+}
+
+/// Returns handlers for all parts of a solver, numbered from 1.
+pub fn all_parts_of<S>() -> Vec<SolverPart>
+where
+    S: Solver + 'static,
+{
+    [
+        ("Part one", S::part_one as PartSolver<S::Parsed>),
+        ("Part two", S::part_two),
+    ]
+    .into_iter()
+    .chain(S::additional_parts().iter().copied())
+    .enumerate()
+    .map(|(index, (title, part))| SolverPart {
+        task: index + 1,
+        title,
+        handler: handler_of::<S>(part),
+    })
+    .collect()
+}
+
+/// Runs every example of a solver and panics on the first wrong answer.
+/// Used by the tests generated for every registered day.
+pub fn assert_examples<S>()
+where
+    S: Solver + 'static,
+{
+    assert!(!S::EXAMPLES.is_empty(), "{} declares no examples", S::TITLE);
+    let parts = all_parts_of::<S>();
+    for example in S::EXAMPLES {
+        for &(part, expected) in example.answers {
+            let solver_part = parts
+                .iter()
+                .find(|solver_part| solver_part.task == part)
+                .unwrap_or_else(|| panic!("{} has no part {}", S::TITLE, part));
+            let actual = (solver_part.handler)(example.input)
+                .unwrap_or_else(|error| panic!("{} part {}: {}", S::TITLE, part, error));
+            assert_eq!(
+                expected,
+                actual.answer.to_string(),
+                "{} part {} with input:\n{}",
+                S::TITLE,
+                part,
+                example.input
+            );
+        }
+    }
+}
@@ -0,0 +1,23 @@
+/// Renders rows as columns separated by '|', each column as wide as its widest cell.
+pub fn render_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.chars().count()).collect();
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    let header: Vec<String> = header.iter().map(|cell| cell.to_string()).collect();
+    let separator: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+    for row in [&header, &separator].into_iter().chain(rows.iter()) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        table.push_str(line.join(" | ").trim_end());
+        table.push('\n');
+    }
+    table
+}
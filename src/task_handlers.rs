@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use crate::answers::ExpectedAnswers;
+use crate::days;
+use crate::error::{AocError, AocResult};
+use crate::solver::{self, Annotator, Example, Handler, Solver, SolverPart};
+use crate::table;
+
+pub struct RegisteredTask {
+    pub title: String,
+    /// Type name of the solver, used to name conflicting registrations.
+    pub registered_by: &'static str,
+    pub handler: Handler,
+    /// Embedded example input of this task with its expected answer.
+    pub example: Option<(&'static str, &'static str)>,
+    /// Renders the annotated grid of the day this task belongs to.
+    pub annotator: Annotator,
+}
+
+/// Handlers keyed by day and task. Ordered so that iterating goes through the days in sequence.
+pub type TaskHandlers = BTreeMap<(usize, usize), RegisteredTask>;
+
+/// Entry of one day, created by the macro `days!` in [`crate::days`].
+pub struct RegisteredDay {
+    pub day: usize,
+    pub title: &'static str,
+    pub examples: &'static [Example],
+    annotator: Annotator,
+    solver_name: fn() -> &'static str,
+    parts: fn() -> Vec<SolverPart>,
+}
+
+impl RegisteredDay {
+    pub const fn new<S>(day: usize) -> Self
+    where
+        S: Solver + 'static,
+    {
+        Self {
+            day,
+            title: S::TITLE,
+            examples: S::EXAMPLES,
+            annotator: solver::annotator_of::<S>,
+            solver_name: std::any::type_name::<S>,
+            parts: solver::all_parts_of::<S>,
+        }
+    }
+}
+
+pub fn all_task_handlers() -> TaskHandlers {
+    task_handlers_of(days::ALL_DAYS)
+}
+
+/// # Panics
+///
+/// If 2 entries register the same task under the same day.
+/// The panic message names both conflicting solvers.
+pub fn task_handlers_of(days: &[RegisteredDay]) -> TaskHandlers {
+    let mut tasks_handlers: TaskHandlers = Default::default();
+    for registered_day in days {
+        for part in (registered_day.parts)() {
+            let task = RegisteredTask {
+                title: format!("{}: {}", registered_day.title, part.title),
+                registered_by: (registered_day.solver_name)(),
+                handler: part.handler,
+                example: Example::for_part(registered_day.examples, part.task),
+                annotator: registered_day.annotator,
+            };
+            register_handler(&mut tasks_handlers, registered_day.day, part.task, task);
+        }
+    }
+    tasks_handlers
+}
+
+pub fn registered_task(
+    handlers: &TaskHandlers,
+    day: usize,
+    task: usize,
+) -> AocResult<&RegisteredTask> {
+    handlers
+        .get(&(day, task))
+        .ok_or(AocError::UnsupportedTask { day, task })
+}
+
+pub fn handler_for(handlers: &TaskHandlers, day: usize, task: usize) -> AocResult<&Handler> {
+    registered_task(handlers, day, task).map(|registered| &registered.handler)
+}
+
+/// Expected answers of all tasks with an embedded example.
+pub fn example_answers(handlers: &TaskHandlers) -> ExpectedAnswers {
+    handlers
+        .iter()
+        .filter_map(|(&key, registered)| {
+            registered
+                .example
+                .map(|(_, expected)| (key, expected.to_string()))
+        })
+        .collect()
+}
+
+pub fn render_list(handlers: &TaskHandlers) -> String {
+    let rows: Vec<Vec<String>> = handlers
+        .iter()
+        .map(|((day, task), registered)| {
+            vec![day.to_string(), task.to_string(), registered.title.clone()]
+        })
+        .collect();
+    table::render_table(&["Day", "Part", "Title"], &rows)
+}
+
+fn register_handler(handlers: &mut TaskHandlers, day: usize, task: usize, to_add: RegisteredTask) {
+    if let Some(already) = handlers.get(&(day, task)) {
+        panic!(
+            "Registered more than one task handler to task {} under the day {}: first by {}, then by {}",
+            task, day, already.registered_by, to_add.registered_by
+        );
+    }
+    handlers.insert((day, task), to_add);
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::days::{day_1::Day1, day_2::Day2};
+
+    #[test]
+    fn list_all_tasks() {
+        let handlers =
+            task_handlers_of(&[RegisteredDay::new::<Day1>(1), RegisteredDay::new::<Day2>(2)]);
+        let actual = render_list(&handlers);
+        insta::assert_snapshot!(actual);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "first by advent_of_code_2023::days::day_1::Day1, then by advent_of_code_2023::days::day_2::Day2"
+    )]
+    fn name_both_solvers_of_duplicate_registration() {
+        task_handlers_of(&[RegisteredDay::new::<Day1>(1), RegisteredDay::new::<Day2>(1)]);
+    }
+}
@@ -1,4 +1,14 @@
 mod coords;
+mod grid;
 pub mod grid_2d;
 pub use coords::Coords;
+pub use grid::Grid;
+mod grid_view;
+pub use grid_view::GridView;
 pub mod parsing;
+mod point;
+pub mod render;
+pub mod search;
+pub use point::Point;
+mod sparse_grid;
+pub use sparse_grid::SparseGrid;
@@ -0,0 +1,442 @@
+use std::collections::HashSet;
+use std::ops::{Index, IndexMut};
+
+use array2d::Array2D;
+
+use crate::utils::grid_2d::{Direction, EdgeMode};
+use crate::utils::render::RenderStyle;
+use crate::utils::{Coords, GridView};
+
+/// Rectangular grid of cells stored row by row.
+/// Row 0 is the first line of the text the grid was created from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    columns: usize,
+}
+
+impl<T> Grid<T> {
+    /// Returns none if the rows do not all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let num_rows = rows.len();
+        let columns = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != columns) {
+            return None;
+        }
+        let cells = rows.into_iter().flatten().collect();
+        Some(Self {
+            cells,
+            rows: num_rows,
+            columns,
+        })
+    }
+
+    pub fn filled_with(value: T, rows: usize, columns: usize) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; rows * columns],
+            rows,
+            columns,
+        }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn contains(&self, coords: Coords) -> bool {
+        coords.y < self.rows && coords.x < self.columns
+    }
+
+    pub fn get(&self, coords: Coords) -> Option<&T> {
+        self.index_of(coords).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, coords: Coords) -> Option<&mut T> {
+        self.index_of(coords).map(|index| &mut self.cells[index])
+    }
+
+    /// Every coordinate of the grid row by row.
+    pub fn all_coords(&self) -> impl Iterator<Item = Coords> {
+        let columns = self.columns;
+        (0..self.rows).flat_map(move |y| (0..columns).map(move |x| Coords { y, x }))
+    }
+
+    /// Every cell with its coordinate row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coords, &T)> {
+        self.all_coords().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coords, &mut T)> {
+        self.all_coords().zip(self.cells.iter_mut())
+    }
+
+    /// Cells of one row from left to right. Empty if the row is out of bounds.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.as_view().row(y)
+    }
+
+    /// Cells of one column from top to bottom. Empty if the column is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.as_view().column(x)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        self.as_view().rows()
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        self.as_view().columns()
+    }
+
+    /// Up to 4 orthogonal neighbors inside the grid in reading order.
+    pub fn neighbors4(&self, coords: Coords) -> impl Iterator<Item = (Coords, &T)> {
+        self.as_view().neighbors4(coords)
+    }
+
+    /// Up to 8 neighbors inside the grid in reading order, diagonal ones included.
+    pub fn neighbors8(&self, coords: Coords) -> impl Iterator<Item = (Coords, &T)> {
+        self.as_view().neighbors8(coords)
+    }
+
+    /// Moves the given number of steps. Returns none if the grid would be left on the way.
+    pub fn step(&self, coords: Coords, direction: Direction, steps: usize) -> Option<Coords> {
+        self.step_with(coords, direction, steps, EdgeMode::Bounded)
+    }
+
+    /// Moves the given number of steps, re-entering at the opposite edge when leaving the grid.
+    /// Returns none only if the start is outside the grid.
+    pub fn step_wrapping(
+        &self,
+        coords: Coords,
+        direction: Direction,
+        steps: usize,
+    ) -> Option<Coords> {
+        self.step_with(coords, direction, steps, EdgeMode::Wrapping)
+    }
+
+    pub fn step_with(
+        &self,
+        coords: Coords,
+        direction: Direction,
+        steps: usize,
+        mode: EdgeMode,
+    ) -> Option<Coords> {
+        self.as_view().step_with(coords, direction, steps, mode)
+    }
+
+    /// One line per row with every cell mapped to a character.
+    pub fn render(&self, to_char: impl Fn(&T) -> char) -> String {
+        self.as_view().render(to_char)
+    }
+
+    /// Like [`Grid::render`] with the given cells highlighted according to the style.
+    pub fn render_highlighted(
+        &self,
+        to_char: impl Fn(&T) -> char,
+        highlighted: &HashSet<Coords>,
+        style: RenderStyle,
+    ) -> String {
+        self.as_view()
+            .render_highlighted(to_char, highlighted, style)
+    }
+
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView::new(self)
+    }
+
+    /// Borrowed window with the given top left corner and size.
+    /// Returns none if the window does not fit into the grid.
+    pub fn view(&self, top_left: Coords, rows: usize, columns: usize) -> Option<GridView<'_, T>> {
+        self.as_view().view(top_left, rows, columns)
+    }
+
+    /// Rows become columns: the cell at (y, x) moves to (x, y).
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remapped(self.columns, self.rows, |Coords { y, x }| Coords {
+            y: x,
+            x: y,
+        })
+    }
+
+    /// Rotated by 90 degrees clockwise: the first column read bottom up becomes the first row.
+    pub fn rotated_90(&self) -> Self
+    where
+        T: Clone,
+    {
+        let rows = self.rows;
+        self.remapped(self.columns, self.rows, |Coords { y, x }| Coords {
+            y: rows - 1 - x,
+            x: y,
+        })
+    }
+
+    pub fn rotated_180(&self) -> Self
+    where
+        T: Clone,
+    {
+        let (rows, columns) = (self.rows, self.columns);
+        self.remapped(rows, columns, |Coords { y, x }| Coords {
+            y: rows - 1 - y,
+            x: columns - 1 - x,
+        })
+    }
+
+    /// Rotated by 270 degrees clockwise: the last column read top down becomes the first row.
+    pub fn rotated_270(&self) -> Self
+    where
+        T: Clone,
+    {
+        let columns = self.columns;
+        self.remapped(self.columns, self.rows, |Coords { y, x }| Coords {
+            y: x,
+            x: columns - 1 - y,
+        })
+    }
+
+    /// Mirrored at the vertical axis: every row is reversed.
+    pub fn flipped_horizontally(&self) -> Self
+    where
+        T: Clone,
+    {
+        let columns = self.columns;
+        self.remapped(self.rows, columns, |Coords { y, x }| Coords {
+            y,
+            x: columns - 1 - x,
+        })
+    }
+
+    /// Mirrored at the horizontal axis: the order of the rows is reversed.
+    pub fn flipped_vertically(&self) -> Self
+    where
+        T: Clone,
+    {
+        let rows = self.rows;
+        self.remapped(rows, self.columns, |Coords { y, x }| Coords {
+            y: rows - 1 - y,
+            x,
+        })
+    }
+
+    /// New grid of the given size where every cell is copied from the returned source coordinate.
+    fn remapped(&self, rows: usize, columns: usize, source_of: impl Fn(Coords) -> Coords) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..rows)
+            .flat_map(|y| (0..columns).map(move |x| Coords { y, x }))
+            .map(|coords| self[source_of(coords)].clone())
+            .collect();
+        Self {
+            cells,
+            rows,
+            columns,
+        }
+    }
+
+    fn index_of(&self, coords: Coords) -> Option<usize> {
+        self.contains(coords)
+            .then(|| coords.y * self.columns + coords.x)
+    }
+}
+
+impl<T> Index<Coords> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// If the coordinate is outside the grid.
+    fn index(&self, coords: Coords) -> &Self::Output {
+        self.get(coords)
+            .unwrap_or_else(|| panic!("Coordinate {} is outside the grid", coords))
+    }
+}
+
+impl<T> IndexMut<Coords> for Grid<T> {
+    fn index_mut(&mut self, coords: Coords) -> &mut Self::Output {
+        self.get_mut(coords)
+            .unwrap_or_else(|| panic!("Coordinate {} is outside the grid", coords))
+    }
+}
+
+impl<T: Clone> From<Array2D<T>> for Grid<T> {
+    fn from(value: Array2D<T>) -> Self {
+        Self {
+            rows: value.num_rows(),
+            columns: value.num_columns(),
+            cells: value.as_row_major(),
+        }
+    }
+}
+
+impl<T: Clone> From<Grid<T>> for Array2D<T> {
+    fn from(value: Grid<T>) -> Self {
+        Array2D::from_row_major(&value.cells, value.rows, value.columns)
+            .expect("The cells fill all rows and columns")
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// The letters "abcd", "efgh" and "ijkl" as rows, shared with the tests of the views.
+    pub(crate) fn grid() -> Grid<char> {
+        Grid::from_rows(vec![
+            vec!['a', 'b', 'c', 'd'],
+            vec!['e', 'f', 'g', 'h'],
+            vec!['i', 'j', 'k', 'l'],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn neighbors_inside_bounds() {
+        assert_case(Coords::new(0, 0), false, "bfe");
+        assert_case(Coords::new(0, 0), true, "be");
+        assert_case(Coords::new(1, 1), false, "abcegijk");
+        assert_case(Coords::new(1, 1), true, "bejg");
+        assert_case(Coords::new(2, 3), false, "ghk");
+        assert_case(Coords::new(5, 5), false, "");
+        fn assert_case(coords: Coords, orthogonal: bool, expected: &str) {
+            let grid = grid();
+            let actual: String = if orthogonal {
+                grid.neighbors4(coords).map(|(_, cell)| cell).collect()
+            } else {
+                grid.neighbors8(coords).map(|(_, cell)| cell).collect()
+            };
+            let mut actual: Vec<char> = actual.chars().collect();
+            let mut expected: Vec<char> = expected.chars().collect();
+            actual.sort();
+            expected.sort();
+            assert_eq!(expected, actual, "Coords: {}", coords);
+        }
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        let rows: Vec<String> = grid.rows().map(|row| row.collect()).collect();
+        assert_eq!(vec!["abcd", "efgh", "ijkl"], rows);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(vec!["aei", "bfj", "cgk", "dhl"], columns);
+        assert_eq!(0, grid.row(3).count());
+        assert_eq!(0, grid.column(4).count());
+    }
+
+    #[test]
+    fn render_rows_as_lines() {
+        let actual = grid().render(|cell| cell.to_ascii_uppercase());
+        assert_eq!("ABCD\nEFGH\nIJKL\n", actual);
+    }
+
+    #[test]
+    fn transform() {
+        let grid = grid();
+        assert_case(grid.transposed(), "aei\nbfj\ncgk\ndhl\n");
+        assert_case(grid.rotated_90(), "iea\njfb\nkgc\nlhd\n");
+        assert_case(grid.rotated_180(), "lkji\nhgfe\ndcba\n");
+        assert_case(grid.rotated_270(), "dhl\ncgk\nbfj\naei\n");
+        assert_case(grid.flipped_horizontally(), "dcba\nhgfe\nlkji\n");
+        assert_case(grid.flipped_vertically(), "ijkl\nefgh\nabcd\n");
+        assert_case(Grid::filled_with('a', 0, 3).rotated_90(), "\n\n\n");
+        fn assert_case(actual: Grid<char>, expected: &str) {
+            assert_eq!(expected, actual.render(|cell| *cell));
+        }
+    }
+
+    #[test]
+    fn mutate_cells() {
+        let mut grid = grid();
+        grid[Coords::new(2, 1)] = 'x';
+        *grid.get_mut(Coords::new(0, 3)).unwrap() = 'y';
+        assert!(grid.get_mut(Coords::new(3, 0)).is_none());
+        let rows: Vec<String> = grid.rows().map(|row| row.collect()).collect();
+        assert_eq!(vec!["abcy", "efgh", "ixkl"], rows);
+    }
+
+    #[test]
+    fn all_coords_of_edge_shapes() {
+        assert_case(1, 3);
+        assert_case(3, 1);
+        assert_case(0, 0);
+        assert_case(0, 4);
+        assert_case(4, 0);
+        fn assert_case(rows: usize, columns: usize) {
+            let grid = Grid::filled_with((), rows, columns);
+            let actual: Vec<Coords> = grid.all_coords().collect();
+            let expected: Vec<Coords> = (0..rows)
+                .flat_map(|y| (0..columns).map(move |x| Coords::new(y, x)))
+                .collect();
+            assert_eq!(expected, actual, "Rows: {}, columns: {}", rows, columns);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn iter_visits_every_cell_once(rows in 0..20usize, columns in 0..20usize) {
+            let grid = Grid::from_rows(
+                (0..rows)
+                    .map(|y| (0..columns).map(|x| y * columns + x).collect())
+                    .collect(),
+            )
+            .unwrap();
+            let visited: HashSet<Coords> = grid.all_coords().collect();
+            prop_assert_eq!(rows * columns, visited.len());
+            prop_assert_eq!(rows * columns, grid.iter().count());
+            for (coords, cell) in grid.iter() {
+                prop_assert!(grid.contains(coords), "Out of bounds: {}", coords);
+                prop_assert_eq!(coords.y * columns + coords.x, *cell);
+            }
+        }
+
+        #[test]
+        fn transformations_compose(rows in 0..8usize, columns in 0..8usize, seed in any::<u64>()) {
+            let grid = Grid::from_rows(
+                (0..rows)
+                    .map(|y| (0..columns).map(|x| seed.wrapping_mul((y * columns + x) as u64)).collect())
+                    .collect(),
+            )
+            .unwrap_or_else(|| Grid::filled_with(seed, rows, columns));
+            prop_assert_eq!(&grid, &grid.transposed().transposed());
+            prop_assert_eq!(&grid, &grid.rotated_90().rotated_90().rotated_90().rotated_90());
+            prop_assert_eq!(grid.rotated_180(), grid.rotated_90().rotated_90());
+            prop_assert_eq!(grid.rotated_270(), grid.rotated_180().rotated_90());
+            prop_assert_eq!(grid.rotated_90(), grid.transposed().flipped_horizontally());
+            prop_assert_eq!(grid.rotated_180(), grid.flipped_horizontally().flipped_vertically());
+            prop_assert_eq!(&grid, &grid.as_view().to_grid());
+        }
+
+        #[test]
+        fn neighbors_stay_in_bounds(rows in 1..20usize, columns in 1..20usize, y in 0..20usize, x in 0..20usize) {
+            let grid = Grid::filled_with((), rows, columns);
+            let coords = Coords::new(y % rows, x % columns);
+            let neighbors: HashSet<Coords> = grid.neighbors8(coords).map(|(moved, _)| moved).collect();
+            prop_assert_eq!(grid.neighbors8(coords).count(), neighbors.len());
+            for moved in neighbors {
+                prop_assert!(grid.contains(moved), "Out of bounds: {}", moved);
+                prop_assert!(moved != coords);
+                prop_assert!(moved.y.abs_diff(coords.y) <= 1 && moved.x.abs_diff(coords.x) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn reject_ragged_rows() {
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    }
+}
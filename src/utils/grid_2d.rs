@@ -1,80 +1,377 @@
 use crate::utils::Coords;
 use array2d::Array2D;
 
+mod from_text;
+mod regions;
+pub use from_text::{try_text_to_grid, TextGridOptions};
+pub use regions::{connected_components, flood_fill, Connectivity, Region};
+
+/// Trims every line. See [`try_text_to_grid`] for a fallible variant with more options.
+///
+/// # Panics
+///
+/// If the trimmed lines differ in length. The message names the first ragged line.
 pub fn text_to_grid<T>(text: &str, on_map: impl Fn(char) -> T) -> Array2D<T>
 where
     T: Clone,
 {
-    let chars: Vec<Vec<T>> = text
-        .lines()
-        .map(|text| text.trim().chars().map(|next| on_map(next)).collect())
-        .collect();
-    Array2D::from_rows(&chars).unwrap()
+    try_text_to_grid(text, &TextGridOptions::trimmed(), on_map)
+        .unwrap_or_else(|error| panic!("{}", error.diagnostic()))
+        .into()
 }
 pub fn access_grid<T>(grid: &Array2D<T>, coords: Coords) -> &T {
     grid.get(coords.y, coords.x).unwrap()
 }
 
+/// Every coordinate of the grid row by row.
 pub fn all_coords<T>(grid: &Array2D<T>) -> impl Iterator<Item = Coords> {
     let columns = grid.num_columns();
-    let rows = grid.num_rows();
-    (0..rows)
-        .map(move |row| {
-            std::iter::repeat(row)
-                .take(columns)
-                .enumerate()
-                .map(|(row, column)| Coords { y: row, x: column })
-        })
-        .flatten()
+    (0..grid.num_rows()).flat_map(move |y| (0..columns).map(move |x| Coords { y, x }))
 }
 
 pub type MaybeNextCell<'a, T> = Option<(&'a T, Coords)>;
 
-pub fn go_left<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<T> {
-    let (y, x) = coords.into();
-    match x.overflowing_sub(1) {
-        (_, true) => None,
-        (new_column, false) => Some((grid.get(y, new_column)?, Coords { x: new_column, y })),
-    }
+/// Heading on a grid where up is towards row 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
 }
-pub fn go_right<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<T> {
-    let (y, x) = coords.into();
-    let new_column = x + 1;
-    if new_column < grid.num_columns() {
-        Some((grid.get(y, new_column)?, Coords { x: new_column, y }))
-    } else {
-        None
-    }
+
+/// How a movement behaves at the edges of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeMode {
+    /// Leaving the grid is not possible.
+    #[default]
+    Bounded,
+    /// Leaving the grid re-enters it at the opposite edge like on a torus.
+    Wrapping,
 }
-pub fn go_up<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<T> {
-    let (y, x) = coords.into();
-    match y.overflowing_sub(1) {
-        (_, true) => None,
-        (new_row, false) => Some((grid.get(new_row, x)?, Coords { x, y: new_row })),
+
+impl Direction {
+    /// The 4 directions without diagonals in reading order.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Left,
+        Direction::Right,
+        Direction::Down,
+    ];
+    /// All 8 directions in reading order.
+    pub const ALL: [Direction; 8] = [
+        Direction::UpLeft,
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Left,
+        Direction::Right,
+        Direction::DownLeft,
+        Direction::Down,
+        Direction::DownRight,
+    ];
+
+    /// Change of row and column for one step.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        let (dy, dx) = self.offset();
+        dy != 0 && dx != 0
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_clockwise(4)
+    }
+
+    /// Turns by 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.turn_clockwise(2)
+    }
+
+    /// Turns by 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.turn_clockwise(-2)
+    }
+
+    /// Turns clockwise by the given number of 45 degree steps. Negative values turn counterclockwise.
+    pub fn turn_clockwise(self, eighths: isize) -> Self {
+        const CLOCKWISE: [Direction; 8] = [
+            Direction::Up,
+            Direction::UpRight,
+            Direction::Right,
+            Direction::DownRight,
+            Direction::Down,
+            Direction::DownLeft,
+            Direction::Left,
+            Direction::UpLeft,
+        ];
+        let index = (self as isize + eighths).rem_euclid(8);
+        CLOCKWISE[index as usize]
     }
 }
-pub fn go_down<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<T> {
-    let (y, x) = coords.into();
-    let new_row = y + 1;
-    if new_row < grid.num_rows() {
-        Some((grid.get(new_row, x)?, Coords { x, y: new_row }))
-    } else {
-        None
+
+/// Moves the given number of steps within a grid of the given rows and columns.
+/// Returns none if the start is outside the grid or a bounded movement would leave it.
+pub fn step_within(
+    (rows, columns): (usize, usize),
+    coords: Coords,
+    direction: Direction,
+    steps: usize,
+    mode: EdgeMode,
+) -> Option<Coords> {
+    if coords.y >= rows || coords.x >= columns {
+        return None;
     }
+    let (dy, dx) = direction.offset();
+    let moved = match mode {
+        EdgeMode::Bounded => {
+            let steps = isize::try_from(steps).ok()?;
+            Coords {
+                y: coords.y.checked_add_signed(dy.checked_mul(steps)?)?,
+                x: coords.x.checked_add_signed(dx.checked_mul(steps)?)?,
+            }
+        }
+        EdgeMode::Wrapping => Coords {
+            y: wrap(coords.y, dy, steps, rows),
+            x: wrap(coords.x, dx, steps, columns),
+        },
+    };
+    (moved.y < rows && moved.x < columns).then_some(moved)
+}
+
+fn wrap(position: usize, delta: isize, steps: usize, length: usize) -> usize {
+    let shift = (steps % length) as isize * delta;
+    (position as isize + shift).rem_euclid(length as isize) as usize
 }
-pub fn go_left_up<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<T> {
-    let (_, moved) = go_left(grid, coords)?;
-    go_up(grid, moved)
+
+pub fn step<T>(
+    grid: &Array2D<T>,
+    coords: Coords,
+    direction: Direction,
+    steps: usize,
+) -> Option<Coords> {
+    step_within(
+        (grid.num_rows(), grid.num_columns()),
+        coords,
+        direction,
+        steps,
+        EdgeMode::Bounded,
+    )
+}
+
+pub fn step_wrapping<T>(
+    grid: &Array2D<T>,
+    coords: Coords,
+    direction: Direction,
+    steps: usize,
+) -> Option<Coords> {
+    step_within(
+        (grid.num_rows(), grid.num_columns()),
+        coords,
+        direction,
+        steps,
+        EdgeMode::Wrapping,
+    )
 }
-pub fn go_right_up<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<T> {
-    let (_, moved) = go_right(grid, coords)?;
-    go_up(grid, moved)
+
+/// One step into the direction together with the cell there.
+pub fn go<T>(grid: &Array2D<T>, coords: Coords, direction: Direction) -> MaybeNextCell<'_, T> {
+    let moved = step(grid, coords, direction, 1)?;
+    Some((access_grid(grid, moved), moved))
+}
+
+pub fn go_left<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<'_, T> {
+    go(grid, coords, Direction::Left)
 }
-pub fn go_left_down<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<T> {
-    let (_, moved) = go_left(grid, coords)?;
-    go_down(grid, moved)
+pub fn go_right<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<'_, T> {
+    go(grid, coords, Direction::Right)
 }
-pub fn go_right_down<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<T> {
-    let (_, moved) = go_right(grid, coords)?;
-    go_down(grid, moved)
+pub fn go_up<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<'_, T> {
+    go(grid, coords, Direction::Up)
+}
+pub fn go_down<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<'_, T> {
+    go(grid, coords, Direction::Down)
+}
+pub fn go_left_up<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<'_, T> {
+    go(grid, coords, Direction::UpLeft)
+}
+pub fn go_right_up<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<'_, T> {
+    go(grid, coords, Direction::UpRight)
+}
+pub fn go_left_down<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<'_, T> {
+    go(grid, coords, Direction::DownLeft)
+}
+pub fn go_right_down<T>(grid: &Array2D<T>, coords: Coords) -> MaybeNextCell<'_, T> {
+    go(grid, coords, Direction::DownRight)
+}
+
+#[cfg(test)]
+mod testing {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    type Move = for<'a> fn(&'a Array2D<usize>, Coords) -> MaybeNextCell<'a, usize>;
+
+    const ALL_MOVES: [Move; 8] = [
+        go_left,
+        go_right,
+        go_up,
+        go_down,
+        go_left_up,
+        go_right_up,
+        go_left_down,
+        go_right_down,
+    ];
+
+    /// Every cell holds its own index in row major order.
+    fn numbered_grid(rows: usize, columns: usize) -> Array2D<usize> {
+        Array2D::filled_by_row_major(
+            {
+                let mut next = 0;
+                move || {
+                    next += 1;
+                    next - 1
+                }
+            },
+            rows,
+            columns,
+        )
+    }
+
+    #[test]
+    fn all_coords_of_edge_shapes() {
+        assert_case(2, 3, &[(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]);
+        assert_case(1, 3, &[(0, 0), (0, 1), (0, 2)]);
+        assert_case(3, 1, &[(0, 0), (1, 0), (2, 0)]);
+        assert_case(0, 0, &[]);
+        assert_case(0, 4, &[]);
+        fn assert_case(rows: usize, columns: usize, expected: &[(usize, usize)]) {
+            let grid = numbered_grid(rows, columns);
+            let actual: Vec<Coords> = all_coords(&grid).collect();
+            let expected: Vec<Coords> = expected.iter().copied().map(Coords::from).collect();
+            assert_eq!(expected, actual, "Rows: {}, columns: {}", rows, columns);
+        }
+    }
+
+    #[test]
+    fn moves_at_borders_of_single_row() {
+        let grid = numbered_grid(1, 3);
+        assert_eq!(None, go_up(&grid, Coords::new(0, 1)));
+        assert_eq!(None, go_down(&grid, Coords::new(0, 1)));
+        assert_eq!(None, go_left(&grid, Coords::new(0, 0)));
+        assert_eq!(None, go_right(&grid, Coords::new(0, 2)));
+        assert_eq!(
+            Some((&2, Coords::new(0, 2))),
+            go_right(&grid, Coords::new(0, 1))
+        );
+    }
+
+    #[test]
+    fn turn_directions() {
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+        assert_eq!(Direction::Up, Direction::Left.turn_right());
+        assert_eq!(Direction::DownRight, Direction::DownLeft.turn_left());
+        assert_eq!(Direction::UpRight, Direction::Up.turn_clockwise(1));
+        assert_eq!(Direction::UpLeft, Direction::Up.turn_clockwise(-1));
+        assert_eq!(Direction::DownLeft, Direction::UpRight.opposite());
+        for direction in Direction::ALL {
+            assert_eq!(direction, direction.opposite().opposite());
+            assert_eq!(direction, direction.turn_right().turn_left());
+            let (dy, dx) = direction.offset();
+            assert_eq!((-dy, -dx), direction.opposite().offset());
+        }
+        assert!(Direction::ORTHOGONAL
+            .iter()
+            .all(|direction| !direction.is_diagonal()));
+    }
+
+    #[test]
+    fn step_several_times() {
+        let grid = numbered_grid(3, 5);
+        assert_case(
+            &grid,
+            (1, 1),
+            Direction::Right,
+            3,
+            Some((1, 4)),
+            Some((1, 4)),
+        );
+        assert_case(&grid, (1, 1), Direction::Right, 4, None, Some((1, 0)));
+        assert_case(&grid, (0, 0), Direction::UpLeft, 1, None, Some((2, 4)));
+        assert_case(&grid, (2, 3), Direction::Down, 7, None, Some((0, 3)));
+        assert_case(&grid, (2, 3), Direction::Up, 0, Some((2, 3)), Some((2, 3)));
+        assert_case(&grid, (3, 0), Direction::Up, 1, None, None);
+        assert_case(
+            &grid,
+            (0, 0),
+            Direction::Down,
+            usize::MAX,
+            None,
+            Some((0, 0)),
+        );
+        fn assert_case(
+            grid: &Array2D<usize>,
+            start: (usize, usize),
+            direction: Direction,
+            steps: usize,
+            bounded: Option<(usize, usize)>,
+            wrapping: Option<(usize, usize)>,
+        ) {
+            let start = Coords::from(start);
+            let actual = step(grid, start, direction, steps);
+            assert_eq!(bounded.map(Coords::from), actual, "Bounded from {}", start);
+            let actual = step_wrapping(grid, start, direction, steps);
+            assert_eq!(
+                wrapping.map(Coords::from),
+                actual,
+                "Wrapping from {}",
+                start
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn all_coords_visit_every_cell_once(rows in 0..20usize, columns in 0..20usize) {
+            let grid = numbered_grid(rows, columns);
+            let visited: Vec<Coords> = all_coords(&grid).collect();
+            let unique: HashSet<Coords> = visited.iter().copied().collect();
+            prop_assert_eq!(rows * columns, visited.len());
+            prop_assert_eq!(visited.len(), unique.len());
+            for coords in visited {
+                prop_assert!(coords.y < rows && coords.x < columns, "Out of bounds: {}", coords);
+                prop_assert_eq!(coords.y * columns + coords.x, *access_grid(&grid, coords));
+            }
+        }
+
+        #[test]
+        fn moves_stay_in_bounds(rows in 1..20usize, columns in 1..20usize, y in 0..20usize, x in 0..20usize) {
+            let grid = numbered_grid(rows, columns);
+            let coords = Coords::new(y % rows, x % columns);
+            for go in ALL_MOVES {
+                if let Some((cell, moved)) = go(&grid, coords) {
+                    prop_assert!(moved.y < rows && moved.x < columns, "Out of bounds: {}", moved);
+                    prop_assert!(moved.y.abs_diff(coords.y) <= 1 && moved.x.abs_diff(coords.x) <= 1);
+                    prop_assert_eq!(access_grid(&grid, moved), cell);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,141 @@
+use crate::error::ParseError;
+use crate::utils::Grid;
+
+/// How [`try_text_to_grid`] treats the lines of the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextGridOptions<T> {
+    /// Removes whitespace at both ends of every line first.
+    /// Off by default since leading spaces are meaningful in some grid formats.
+    pub trim: bool,
+    /// Pads shorter lines up to the longest one instead of rejecting them.
+    pub fill: Option<T>,
+}
+
+impl<T> Default for TextGridOptions<T> {
+    fn default() -> Self {
+        Self {
+            trim: false,
+            fill: None,
+        }
+    }
+}
+
+impl<T> TextGridOptions<T> {
+    pub fn trimmed() -> Self {
+        Self {
+            trim: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn padded_with(self, fill: T) -> Self {
+        Self {
+            fill: Some(fill),
+            ..self
+        }
+    }
+}
+
+/// One row per line and one cell per character.
+/// Without a fill value the first line whose width differs from the first row is reported.
+pub fn try_text_to_grid<T>(
+    text: &str,
+    options: &TextGridOptions<T>,
+    on_map: impl Fn(char) -> T,
+) -> Result<Grid<T>, ParseError>
+where
+    T: Clone,
+{
+    let lines: Vec<&str> = text.lines().collect();
+    let mut rows: Vec<Vec<T>> = lines
+        .iter()
+        .map(|line| cells_of(line, options).chars().map(&on_map).collect())
+        .collect();
+    match &options.fill {
+        Some(fill) => {
+            let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+            for row in rows.iter_mut() {
+                row.resize(width, fill.clone());
+            }
+        }
+        None => {
+            let width = rows.first().map_or(0, Vec::len);
+            if let Some((index, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width)
+            {
+                // Underlines the cells too many or points right after a row which is too short.
+                let offset = leading_whitespace(lines[index], options);
+                let columns = offset + width.min(row.len())..offset + row.len();
+                return Err(ParseError::new(format!(
+                    "Expected {} cells as in the first row, found {}",
+                    width,
+                    row.len()
+                ))
+                .at_line(index + 1)
+                .in_text(lines[index], columns));
+            }
+        }
+    }
+    Ok(Grid::from_rows(rows).expect("All rows have the same width"))
+}
+
+/// Part of the line which holds the cells.
+fn cells_of<'a, T>(line: &'a str, options: &TextGridOptions<T>) -> &'a str {
+    if options.trim {
+        line.trim()
+    } else {
+        line
+    }
+}
+
+/// Characters in front of the cells of the line which are trimmed away.
+fn leading_whitespace<T>(line: &str, options: &TextGridOptions<T>) -> usize {
+    if options.trim {
+        line.chars().count() - line.trim_start().chars().count()
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn report_first_ragged_line() {
+        let actual =
+            try_text_to_grid("abc\nabc\nab\nabcd", &TextGridOptions::default(), |c| c).unwrap_err();
+        assert_eq!(
+            "Could not parse input at line 3, column 3: Expected 3 cells as in the first row, found 2
+  |
+3 | ab
+  |   ^",
+            actual.diagnostic()
+        );
+        let actual =
+            try_text_to_grid("abc\n  abcde ", &TextGridOptions::trimmed(), |c| c).unwrap_err();
+        assert_eq!(Some(6), actual.column());
+        let actual =
+            try_text_to_grid("abc\nabcde", &TextGridOptions::default(), |c| c).unwrap_err();
+        assert_eq!(Some("de".to_string()), actual.offending_text());
+    }
+
+    #[test]
+    fn pad_ragged_lines() {
+        let options = TextGridOptions::default().padded_with('.');
+        let actual = try_text_to_grid("ab\n  a\n", &options, |c| c).unwrap();
+        assert_eq!("ab.\n  a\n", actual.render(|cell| *cell));
+    }
+
+    #[test]
+    fn trim_only_if_asked_for() {
+        assert_case(&TextGridOptions::default(), Some(" a \n b \n"));
+        assert_case(&TextGridOptions::trimmed(), Some("a\nb\n"));
+        assert_case(&TextGridOptions::trimmed().padded_with('.'), Some("a\nb\n"));
+        fn assert_case(options: &TextGridOptions<char>, expected: Option<&str>) {
+            let actual = try_text_to_grid(" a \n b \n", options, |c| c)
+                .ok()
+                .map(|grid| grid.render(|cell| *cell));
+            assert_eq!(expected.map(String::from), actual, "Options: {:?}", options);
+        }
+    }
+}
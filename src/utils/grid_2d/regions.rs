@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+
+use crate::utils::grid_2d::Direction;
+use crate::utils::{search, Coords, Grid};
+
+/// Which neighboring cells belong to the same region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left and right.
+    Orthogonal,
+    /// All 8 surrounding cells, diagonal ones included.
+    All,
+    /// Only left and right, so every region is a run of cells within one row.
+    RowRuns,
+}
+
+impl Connectivity {
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Connectivity::Orthogonal => &Direction::ORTHOGONAL,
+            Connectivity::All => &Direction::ALL,
+            Connectivity::RowRuns => &[Direction::Left, Direction::Right],
+        }
+    }
+}
+
+/// Connected cells of a grid in reading order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub cells: Vec<Coords>,
+    /// Smallest row and smallest column of all cells.
+    pub top_left: Coords,
+    /// Biggest row and biggest column of all cells.
+    pub bottom_right: Coords,
+}
+
+impl Region {
+    /// Returns none if there are no cells.
+    pub fn from_cells(mut cells: Vec<Coords>) -> Option<Self> {
+        cells.sort();
+        cells.dedup();
+        let first = *cells.first()?;
+        let (top_left, bottom_right) = cells.iter().fold((first, first), |(low, high), cell| {
+            (
+                Coords::new(low.y.min(cell.y), low.x.min(cell.x)),
+                Coords::new(high.y.max(cell.y), high.x.max(cell.x)),
+            )
+        });
+        Some(Self {
+            cells,
+            top_left,
+            bottom_right,
+        })
+    }
+
+    pub fn contains(&self, coords: Coords) -> bool {
+        self.cells.binary_search(&coords).is_ok()
+    }
+}
+
+/// Region of all cells reachable from `start` over cells accepted by `belongs`.
+/// Returns none if the start is outside the grid or does not belong itself.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    start: Coords,
+    connectivity: Connectivity,
+    belongs: impl Fn(&T) -> bool,
+) -> Option<Region> {
+    if !grid.get(start).is_some_and(&belongs) {
+        return None;
+    }
+    let reached = search::bfs_distances([start], |&coords| {
+        connectivity
+            .directions()
+            .iter()
+            .filter_map(move |&direction| grid.step(coords, direction, 1))
+            .filter(|&moved| belongs(&grid[moved]))
+            .collect::<Vec<Coords>>()
+    });
+    Region::from_cells(reached.into_keys().collect())
+}
+
+/// Every region of cells accepted by `belongs`, ordered by their first cell in reading order.
+pub fn connected_components<T>(
+    grid: &Grid<T>,
+    connectivity: Connectivity,
+    belongs: impl Fn(&T) -> bool,
+) -> Vec<Region> {
+    let mut labeled: HashSet<Coords> = HashSet::new();
+    let mut regions = Vec::new();
+    for (coords, cell) in grid.iter() {
+        if labeled.contains(&coords) || !belongs(cell) {
+            continue;
+        }
+        let region = flood_fill(grid, coords, connectivity, &belongs)
+            .expect("The start belongs to the region");
+        labeled.extend(region.cells.iter().copied());
+        regions.push(region);
+    }
+    regions
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::utils::grid_2d;
+
+    const SHAPES: &str = "##..#
+#..##
+..#..
+##.#.";
+
+    fn shapes() -> Grid<char> {
+        Grid::from(grid_2d::text_to_grid(SHAPES, |cell| cell))
+    }
+
+    #[test]
+    fn label_regions_by_connectivity() {
+        assert_case(Connectivity::Orthogonal, &[3, 3, 1, 2, 1]);
+        assert_case(Connectivity::All, &[3, 7]);
+        assert_case(Connectivity::RowRuns, &[2, 1, 1, 2, 1, 2, 1]);
+        fn assert_case(connectivity: Connectivity, expected_sizes: &[usize]) {
+            let regions = connected_components(&shapes(), connectivity, |cell| *cell == '#');
+            let actual: Vec<usize> = regions.iter().map(|region| region.cells.len()).collect();
+            assert_eq!(expected_sizes, &actual, "Connectivity: {:?}", connectivity);
+        }
+    }
+
+    #[test]
+    fn flood_fill_with_bounding_box() {
+        let grid = shapes();
+        let actual = flood_fill(&grid, Coords::new(1, 3), Connectivity::All, |cell| {
+            *cell == '#'
+        })
+        .unwrap();
+        assert_eq!(Coords::new(0, 0), actual.top_left);
+        assert_eq!(Coords::new(3, 4), actual.bottom_right);
+        assert!(actual.contains(Coords::new(3, 0)));
+        assert!(!actual.contains(Coords::new(0, 0)));
+        assert_eq!(
+            None,
+            flood_fill(&grid, Coords::new(0, 2), Connectivity::All, |cell| {
+                *cell == '#'
+            })
+        );
+        assert_eq!(
+            None,
+            flood_fill(&grid, Coords::new(9, 9), Connectivity::All, |_| true)
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashSet;
+use std::ops::Index;
+
+use crate::utils::grid_2d::{self, Direction, EdgeMode};
+use crate::utils::render::{self, RenderStyle};
+use crate::utils::{Coords, Grid};
+
+/// Borrowed rectangular window of a [`Grid`] without copying its cells.
+/// Coordinates are relative to the top left corner of the window.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    top_left: Coords,
+    rows: usize,
+    columns: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    /// Window over the whole grid.
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        Self {
+            grid,
+            top_left: Coords::new(0, 0),
+            rows: grid.num_rows(),
+            columns: grid.num_columns(),
+        }
+    }
+
+    pub fn num_rows(self) -> usize {
+        self.rows
+    }
+
+    pub fn num_columns(self) -> usize {
+        self.columns
+    }
+
+    pub fn contains(self, coords: Coords) -> bool {
+        coords.y < self.rows && coords.x < self.columns
+    }
+
+    pub fn get(self, coords: Coords) -> Option<&'a T> {
+        if self.contains(coords) {
+            self.grid.get(self.absolute(coords))
+        } else {
+            None
+        }
+    }
+
+    /// Window inside this window. Returns none if it does not fit.
+    pub fn view(self, top_left: Coords, rows: usize, columns: usize) -> Option<Self> {
+        let fits = top_left
+            .y
+            .checked_add(rows)
+            .is_some_and(|bottom| bottom <= self.rows)
+            && top_left
+                .x
+                .checked_add(columns)
+                .is_some_and(|right| right <= self.columns);
+        fits.then_some(Self {
+            grid: self.grid,
+            top_left: self.absolute(top_left),
+            rows,
+            columns,
+        })
+    }
+
+    /// Every coordinate of the window row by row.
+    pub fn all_coords(self) -> impl Iterator<Item = Coords> {
+        let columns = self.columns;
+        (0..self.rows).flat_map(move |y| (0..columns).map(move |x| Coords { y, x }))
+    }
+
+    /// Every cell with its coordinate row by row.
+    pub fn iter(self) -> impl Iterator<Item = (Coords, &'a T)> + 'a {
+        self.all_coords()
+            .map(move |coords| (coords, &self.grid[self.absolute(coords)]))
+    }
+
+    /// Cells of one row from left to right. Empty if the row is out of bounds.
+    pub fn row(self, y: usize) -> impl Iterator<Item = &'a T> + 'a {
+        let columns = if y < self.rows { self.columns } else { 0 };
+        (0..columns).map(move |x| &self.grid[self.absolute(Coords { y, x })])
+    }
+
+    /// Cells of one column from top to bottom. Empty if the column is out of bounds.
+    pub fn column(self, x: usize) -> impl Iterator<Item = &'a T> + 'a {
+        let rows = if x < self.columns { self.rows } else { 0 };
+        (0..rows).map(move |y| &self.grid[self.absolute(Coords { y, x })])
+    }
+
+    pub fn rows(self) -> impl Iterator<Item = impl Iterator<Item = &'a T> + 'a> + 'a {
+        (0..self.rows).map(move |y| self.row(y))
+    }
+
+    pub fn columns(self) -> impl Iterator<Item = impl Iterator<Item = &'a T> + 'a> + 'a {
+        (0..self.columns).map(move |x| self.column(x))
+    }
+
+    /// Up to 4 orthogonal neighbors inside the window in reading order.
+    pub fn neighbors4(self, coords: Coords) -> impl Iterator<Item = (Coords, &'a T)> + 'a {
+        self.neighbors_by(coords, &Direction::ORTHOGONAL)
+    }
+
+    /// Up to 8 neighbors inside the window in reading order, diagonal ones included.
+    pub fn neighbors8(self, coords: Coords) -> impl Iterator<Item = (Coords, &'a T)> + 'a {
+        self.neighbors_by(coords, &Direction::ALL)
+    }
+
+    pub fn step_with(
+        self,
+        coords: Coords,
+        direction: Direction,
+        steps: usize,
+        mode: EdgeMode,
+    ) -> Option<Coords> {
+        grid_2d::step_within((self.rows, self.columns), coords, direction, steps, mode)
+    }
+
+    /// One line per row with every cell mapped to a character.
+    pub fn render(self, to_char: impl Fn(&T) -> char) -> String {
+        self.render_highlighted(to_char, &HashSet::new(), RenderStyle::Plain)
+    }
+
+    /// Like [`GridView::render`] with the given cells highlighted according to the style.
+    pub fn render_highlighted(
+        self,
+        to_char: impl Fn(&T) -> char,
+        highlighted: &HashSet<Coords>,
+        style: RenderStyle,
+    ) -> String {
+        let to_char = &to_char;
+        let rows = (0..self.rows).map(|y| {
+            (0..self.columns).map(move |x| {
+                let coords = Coords { y, x };
+                (to_char(&self[coords]), highlighted.contains(&coords))
+            })
+        });
+        render::render_lines(rows, style)
+    }
+
+    /// Copies the cells of the window into a grid of their own.
+    pub fn to_grid(self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_rows(self.rows().map(|row| row.cloned().collect()).collect())
+            .unwrap_or_else(|| unreachable!("Rows of a view have the same length"))
+    }
+
+    fn neighbors_by(
+        self,
+        coords: Coords,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Coords, &'a T)> + 'a {
+        directions.iter().filter_map(move |&direction| {
+            let moved = self.step_with(coords, direction, 1, EdgeMode::Bounded)?;
+            Some((moved, &self.grid[self.absolute(moved)]))
+        })
+    }
+
+    fn absolute(self, coords: Coords) -> Coords {
+        Coords {
+            y: self.top_left.y + coords.y,
+            x: self.top_left.x + coords.x,
+        }
+    }
+}
+
+impl<T> Index<Coords> for GridView<'_, T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// If the coordinate is outside the window.
+    fn index(&self, coords: Coords) -> &Self::Output {
+        self.get(coords)
+            .unwrap_or_else(|| panic!("Coordinate {} is outside the view", coords))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::utils::grid::testing::grid;
+
+    #[test]
+    fn access_window_relative_to_its_corner() {
+        let grid = grid();
+        let view = grid.view(Coords::new(1, 1), 2, 2).unwrap();
+        assert_eq!("fg\njk\n", view.render(|cell| *cell));
+        assert_eq!('k', view[Coords::new(1, 1)]);
+        assert_eq!(None, view.get(Coords::new(0, 2)));
+        let columns: Vec<String> = view.columns().map(|column| column.collect()).collect();
+        assert_eq!(vec!["fj", "gk"], columns);
+        let neighbors: String = view
+            .neighbors8(Coords::new(0, 0))
+            .map(|(_, cell)| cell)
+            .collect();
+        assert_eq!("gjk", neighbors);
+        let inner = view.view(Coords::new(1, 0), 1, 2).unwrap();
+        assert_eq!("jk\n", inner.render(|cell| *cell));
+        assert_eq!(
+            grid.view(Coords::new(2, 1), 1, 2).unwrap().to_grid(),
+            inner.to_grid()
+        );
+    }
+
+    #[test]
+    fn reject_windows_outside_grid() {
+        let grid = grid();
+        assert!(grid.view(Coords::new(0, 0), 3, 4).is_some());
+        assert!(grid.view(Coords::new(3, 4), 0, 0).is_some());
+        assert!(grid.view(Coords::new(1, 0), 3, 1).is_none());
+        assert!(grid.view(Coords::new(0, 2), 1, 3).is_none());
+        assert!(grid.view(Coords::new(0, 0), usize::MAX, 1).is_none());
+        let view = grid.view(Coords::new(1, 1), 2, 2).unwrap();
+        assert!(view.view(Coords::new(1, 1), 1, 2).is_none());
+    }
+}
@@ -1,8 +1,26 @@
+mod grammar;
+pub use grammar::{
+    identifier, keyword, literal, many, map, padded, pair, parse_complete, parse_lines, preceded,
+    separated, signed, terminated, try_map, unsigned, whitespace, Failure, PResult, Parser,
+};
+mod numbers;
+pub use numbers::{signed_numbers, unsigned_numbers};
+mod sections;
+pub use sections::{Section, Sections};
+
 pub fn chunks_of_non_empty_lines(text: &str) -> Vec<Vec<&str>> {
-    let mut output: Vec<Vec<&str>> = Vec::with_capacity(text.len());
+    numbered_chunks_of_non_empty_lines(text)
+        .into_iter()
+        .map(|chunk| chunk.into_iter().map(|(_, line)| line).collect())
+        .collect()
+}
+
+/// Like [`chunks_of_non_empty_lines`] with every line paired with its line number starting at 1.
+pub fn numbered_chunks_of_non_empty_lines(text: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut output: Vec<Vec<(usize, &str)>> = Vec::new();
     let mut was_in_empty = true;
 
-    for next_line in text.lines() {
+    for (index, next_line) in text.lines().enumerate() {
         let trimmed = next_line.trim();
         if trimmed.is_empty() {
             was_in_empty = true;
@@ -11,7 +29,7 @@ pub fn chunks_of_non_empty_lines(text: &str) -> Vec<Vec<&str>> {
                 output.push(Vec::new());
             }
             was_in_empty = false;
-            output.last_mut().unwrap().push(next_line);
+            output.last_mut().unwrap().push((index + 1, next_line));
         }
     }
 
@@ -24,15 +42,21 @@ mod testing {
 
     #[test]
     fn parsing_chunks_of_non_empty_lines() {
-        assert_case("", &vec![]);
-        assert_case("aaa\naa\n", &vec![vec!["aaa", "aa"]]);
+        assert_case("", &[]);
+        assert_case("aaa\naa\n", &[vec!["aaa", "aa"]]);
         assert_case(
             "  \naaa\naa\n\n  \nbb\n\n",
-            &vec![vec!["aaa", "aa"], vec!["bb"]],
+            &[vec!["aaa", "aa"], vec!["bb"]],
         );
         fn assert_case(input: &str, expected: &[Vec<&str>]) {
             let actual = chunks_of_non_empty_lines(input);
             assert_eq!(expected, &actual, "Input: {}", input);
         }
     }
+
+    #[test]
+    fn number_lines_of_chunks() {
+        let actual = numbered_chunks_of_non_empty_lines("\naaa\n\nbb\ncc");
+        assert_eq!(vec![vec![(2, "aaa")], vec![(4, "bb"), (5, "cc")]], actual);
+    }
 }
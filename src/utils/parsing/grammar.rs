@@ -0,0 +1,355 @@
+//! Small composable parsers for one line of input.
+//! A parser takes the remaining text and returns the parsed value together with the text after it.
+//! Whole lines are parsed with [`parse_complete`] and whole inputs line by line with [`parse_lines`].
+
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// Value and the text after it or where and why parsing stopped.
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Text at which a parser failed together with what it expected there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    pub expected: String,
+    pub rest: &'a str,
+    /// Bytes at the start of the rest which are reported as the offending text.
+    pub found: usize,
+}
+
+impl<'a> Failure<'a> {
+    /// The offending text is the word or the single other character at the start of the rest.
+    pub fn new(expected: impl Into<String>, rest: &'a str) -> Self {
+        let word = rest.len()
+            - rest
+                .trim_start_matches(|c: char| c.is_alphanumeric() || c == '_')
+                .len();
+        let found = match word {
+            0 => rest.chars().next().map_or(0, char::len_utf8),
+            _ => word,
+        };
+        Self::spanning(expected, rest, found)
+    }
+
+    /// Reports the first `found` bytes of the rest as the offending text.
+    pub fn spanning(expected: impl Into<String>, rest: &'a str, found: usize) -> Self {
+        Self {
+            expected: expected.into(),
+            rest,
+            found,
+        }
+    }
+
+    /// Error pointing at the offending text within the line the parser ran on.
+    fn into_parse_error(self, line: &str) -> ParseError {
+        let offending = &self.rest[..self.found];
+        let message = if offending.is_empty() {
+            format!("Expected {}, found the end of the line", self.expected)
+        } else {
+            format!("Expected {}, found \"{}\"", self.expected, offending)
+        };
+        let start = line[..line.len() - self.rest.len()].chars().count();
+        ParseError::new(message).in_text(line, start..start + offending.chars().count())
+    }
+}
+
+/// Anything which can parse a value from the start of a text.
+/// Implemented for every function or closure with the matching signature.
+pub trait Parser<T> {
+    fn parse<'a>(&self, input: &'a str) -> PResult<'a, T>;
+}
+
+impl<T, F> Parser<T> for F
+where
+    F: for<'a> Fn(&'a str) -> PResult<'a, T>,
+{
+    fn parse<'a>(&self, input: &'a str) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Lets the compiler infer that the output of a closure borrows from its input.
+fn parser<T, F>(function: F) -> F
+where
+    F: for<'a> Fn(&'a str) -> PResult<'a, T>,
+{
+    function
+}
+
+/// Runs the parser on the whole text. Whitespace around it is allowed, any other leftover is an error.
+pub fn parse_complete<T>(parser: &impl Parser<T>, text: &str) -> Result<T, ParseError> {
+    let (value, rest) = padded(self::parser(|input| parser.parse(input)))
+        .parse(text)
+        .map_err(|failure| failure.into_parse_error(text))?;
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(Failure::new("the end of the line", rest).into_parse_error(text))
+    }
+}
+
+/// Runs the parser on every line which is not blank. Errors carry the line number.
+pub fn parse_lines<T>(parser: &impl Parser<T>, text: &str) -> Result<Vec<T>, ParseError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| parse_complete(parser, line).map_err(|error| error.at_line(index + 1)))
+        .collect()
+}
+
+/// Exactly the given text.
+pub fn literal(expected: &'static str) -> impl Parser<()> {
+    parser(move |input| match input.strip_prefix(expected) {
+        Some(rest) => Ok(((), rest)),
+        None => Err(Failure::new(format!("\"{}\"", expected), input)),
+    })
+}
+
+/// Zero or more spaces or tabs. Never fails.
+pub fn whitespace(input: &str) -> PResult<'_, ()> {
+    Ok(((), input.trim_start_matches([' ', '\t'])))
+}
+
+/// Digits without a sign which fit into the target type.
+pub fn unsigned<T: FromStr>(input: &str) -> PResult<'_, T> {
+    let digits = input.len() - input.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    number(input, digits, "a number")
+}
+
+/// Digits with an optional leading '+' or '-' which fit into the target type.
+pub fn signed<T: FromStr>(input: &str) -> PResult<'_, T> {
+    let sign = usize::from(input.starts_with(['+', '-']));
+    let digits = input[sign..].len()
+        - input[sign..]
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    number(input, sign + digits, "a signed number")
+}
+
+fn number<'a, T: FromStr>(input: &'a str, length: usize, expected: &str) -> PResult<'a, T> {
+    let (text, rest) = input.split_at(length);
+    if !text.ends_with(|c: char| c.is_ascii_digit()) {
+        return Err(Failure::new(expected, input));
+    }
+    match text.parse() {
+        Ok(value) => Ok((value, rest)),
+        Err(_) => Err(Failure::new(
+            format!("a number fitting into {}", std::any::type_name::<T>()),
+            input,
+        )),
+    }
+}
+
+/// A letter followed by letters, digits, '_' or '-' like "seed-to-soil".
+pub fn identifier(input: &str) -> PResult<'_, String> {
+    if !input.starts_with(|c: char| c.is_alphabetic()) {
+        return Err(Failure::new("an identifier", input));
+    }
+    let rest = input.trim_start_matches(|c: char| c.is_alphanumeric() || c == '_' || c == '-');
+    let length = input.len() - rest.len();
+    Ok((input[..length].to_string(), rest))
+}
+
+/// One of the given identifiers mapped to its value.
+pub fn keyword<T: Clone>(choices: &'static [(&'static str, T)]) -> impl Parser<T> {
+    parser(move |input| {
+        let expected = || {
+            let names: Vec<&str> = choices.iter().map(|(name, _)| *name).collect();
+            Failure::new(format!("one of {}", names.join(", ")), input)
+        };
+        let (word, rest) = identifier(input).map_err(|_| expected())?;
+        choices
+            .iter()
+            .find(|(name, _)| *name == word)
+            .map(|(_, value)| (value.clone(), rest))
+            .ok_or_else(expected)
+    })
+}
+
+/// Optional whitespace before and after the parser.
+pub fn padded<T>(inner: impl Parser<T>) -> impl Parser<T> {
+    parser(move |input| {
+        let ((), input) = whitespace(input)?;
+        let (value, input) = inner.parse(input)?;
+        let ((), rest) = whitespace(input)?;
+        Ok((value, rest))
+    })
+}
+
+pub fn pair<A, B>(first: impl Parser<A>, second: impl Parser<B>) -> impl Parser<(A, B)> {
+    parser(move |input| {
+        let (a, input) = first.parse(input)?;
+        let (b, rest) = second.parse(input)?;
+        Ok(((a, b), rest))
+    })
+}
+
+/// Value of the second parser after the first one matched.
+pub fn preceded<A, B>(first: impl Parser<A>, second: impl Parser<B>) -> impl Parser<B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+/// Value of the first parser if the second one matches after it.
+pub fn terminated<A, B>(first: impl Parser<A>, second: impl Parser<B>) -> impl Parser<A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+/// At least one item with the separator between each of them.
+pub fn separated<T, S>(item: impl Parser<T>, separator: impl Parser<S>) -> impl Parser<Vec<T>> {
+    parser(move |input| {
+        let (first, mut input) = item.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((_, after_separator)) = separator.parse(input) {
+            let (next, rest) = item.parse(after_separator)?;
+            items.push(next);
+            input = rest;
+        }
+        Ok((items, input))
+    })
+}
+
+/// Zero or more items until the parser fails. The failed attempt consumes nothing.
+pub fn many<T>(item: impl Parser<T>) -> impl Parser<Vec<T>> {
+    parser(move |mut input| {
+        let mut items = Vec::new();
+        while let Ok((next, rest)) = item.parse(input) {
+            if rest.len() == input.len() {
+                break;
+            }
+            items.push(next);
+            input = rest;
+        }
+        Ok((items, input))
+    })
+}
+
+pub fn map<T, U>(inner: impl Parser<T>, mapping: impl Fn(T) -> U) -> impl Parser<U> {
+    parser(move |input| {
+        let (value, rest) = inner.parse(input)?;
+        Ok((mapping(value), rest))
+    })
+}
+
+/// Like [`map`] but the mapping may reject the value.
+/// The error describes what was expected instead of the whole rejected value.
+pub fn try_map<T, U>(
+    inner: impl Parser<T>,
+    mapping: impl Fn(T) -> Result<U, String>,
+) -> impl Parser<U> {
+    parser(move |input| {
+        let (value, rest) = inner.parse(input)?;
+        match mapping(value) {
+            Ok(mapped) => Ok((mapped, rest)),
+            Err(expected) => {
+                let value = input[..input.len() - rest.len()].trim_end();
+                Err(Failure::spanning(expected, input, value.len()))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn parse_numbers() {
+        assert_eq!(Ok((42u32, " rest")), unsigned("42 rest"));
+        assert_eq!(Ok((-7i64, "")), signed("-7"));
+        assert_eq!(Ok((7i64, "x")), signed("+7x"));
+        assert!(unsigned::<u32>("-7").is_err());
+        assert!(signed::<i32>("-").is_err());
+        assert_eq!(
+            Err(Failure::new("a number fitting into u8", "256")),
+            unsigned::<u8>("256")
+        );
+    }
+
+    #[test]
+    fn parse_words() {
+        assert_eq!(
+            Ok(("seed-to-soil".to_string(), " map:")),
+            identifier("seed-to-soil map:")
+        );
+        assert!(identifier("1abc").is_err());
+        let color = keyword(&[("red", 0), ("blue", 1)]);
+        assert_eq!(Ok((1, ",")), color.parse("blue,"));
+        assert_eq!(
+            Err(Failure::new("one of red, blue", "reddish")),
+            color.parse("reddish")
+        );
+    }
+
+    #[test]
+    fn combine_into_grammar() {
+        let set = separated(
+            padded(pair(unsigned::<u32>, padded(identifier))),
+            literal(","),
+        );
+        let line = preceded(
+            pair(literal("Game"), padded(unsigned::<u32>)),
+            preceded(literal(":"), separated(set, literal(";"))),
+        );
+        let actual = parse_complete(&line, "Game 3: 1 red, 2 blue; 4 green ").unwrap();
+        assert_eq!(
+            vec![
+                vec![(1, "red".to_string()), (2, "blue".to_string())],
+                vec![(4, "green".to_string())]
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn report_what_was_expected() {
+        let numbers = many(padded(unsigned::<u32>));
+        assert_case(&numbers, "1 2 3", Ok(vec![1, 2, 3]));
+        assert_case(&numbers, "", Ok(vec![]));
+        assert_case(
+            &numbers,
+            "1 x 3",
+            Err("at column 3: Expected the end of the line, found \"x\""),
+        );
+        let card = preceded(literal("Card"), padded(unsigned::<u32>));
+        assert_case(
+            &card,
+            "Card",
+            Err("at column 5: Expected a number, found the end of the line"),
+        );
+        assert_case(
+            &card,
+            "Cart 1",
+            Err("at column 1: Expected \"Card\", found \"Cart\""),
+        );
+        let three = try_map(many(padded(unsigned::<u32>)), |numbers| match numbers[..] {
+            [a, b, c] => Ok(a + b + c),
+            _ => Err("3 numbers".to_string()),
+        });
+        assert_case(&three, "1 2 3", Ok(6));
+        assert_case(
+            &three,
+            "1 2",
+            Err("at column 1: Expected 3 numbers, found \"1 2\""),
+        );
+        fn assert_case<T: PartialEq + std::fmt::Debug>(
+            parser: &impl Parser<T>,
+            input: &str,
+            expected: Result<T, &str>,
+        ) {
+            let actual = parse_complete(parser, input).map_err(|error| error.to_string());
+            let expected = expected.map_err(|message| format!("Could not parse input {}", message));
+            assert_eq!(expected, actual, "Input: {}", input);
+        }
+    }
+
+    #[test]
+    fn attach_line_numbers() {
+        let actual = parse_lines(&unsigned::<u32>, "1\n\n3\n").unwrap();
+        assert_eq!(vec![1, 3], actual);
+        let actual = parse_lines(&unsigned::<u32>, "1\n\n  x1\n").unwrap_err();
+        assert_eq!(Some(3), actual.line());
+        assert_eq!(Some(3), actual.column());
+        assert_eq!(Some("x1".to_string()), actual.offending_text());
+    }
+}
@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// Every run of digits in the line in order, whatever text surrounds them.
+/// A number which does not fit into the target type is reported with its columns.
+pub fn unsigned_numbers<T: FromStr>(line: &str) -> Result<Vec<T>, ParseError> {
+    numbers(line, false)
+}
+
+/// Like [`unsigned_numbers`] but a '-' or '+' right before the digits is kept as the sign.
+/// A sign right after a letter or digit is not one, so "3-5" yields 3 and 5 and "x=-5" yields -5.
+pub fn signed_numbers<T: FromStr>(line: &str) -> Result<Vec<T>, ParseError> {
+    numbers(line, true)
+}
+
+fn numbers<T: FromStr>(line: &str, signed: bool) -> Result<Vec<T>, ParseError> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut numbers = Vec::new();
+    let mut column = 0;
+    while column < chars.len() {
+        let digits_start = column + usize::from(signed && is_sign_at(&chars, column));
+        let digits = chars[digits_start..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            column += 1;
+            continue;
+        }
+        let end = digits_start + digits;
+        let text: String = chars[column..end].iter().collect();
+        let number = text.parse().map_err(|_| {
+            ParseError::new(format!(
+                "Number {} does not fit into {}",
+                text,
+                std::any::type_name::<T>()
+            ))
+            .in_text(line, column..end)
+        })?;
+        numbers.push(number);
+        column = end;
+    }
+    Ok(numbers)
+}
+
+fn is_sign_at(chars: &[char], column: usize) -> bool {
+    let after_word = column > 0 && chars[column - 1].is_alphanumeric();
+    matches!(chars[column], '-' | '+') && !after_word
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn extract_numbers_from_text() {
+        assert_case("seeds: 79 14 55 13", &[79, 14, 55, 13], &[79, 14, 55, 13]);
+        assert_case("no numbers", &[], &[]);
+        assert_case("x=-5, y=+12", &[5, 12], &[-5, 12]);
+        assert_case("3-5 -7", &[3, 5, 7], &[3, 5, -7]);
+        assert_case("a-1 -", &[1], &[1]);
+        fn assert_case(line: &str, unsigned: &[u32], signed: &[i32]) {
+            assert_eq!(
+                Ok(unsigned.to_vec()),
+                unsigned_numbers(line),
+                "Line: {}",
+                line
+            );
+            assert_eq!(Ok(signed.to_vec()), signed_numbers(line), "Line: {}", line);
+        }
+    }
+
+    #[test]
+    fn report_overflow_with_columns() {
+        let actual = unsigned_numbers::<u8>("1 255 256").unwrap_err();
+        assert_eq!(
+            "Could not parse input at column 7: Number 256 does not fit into u8",
+            actual.to_string()
+        );
+        assert_eq!(Some("256".to_string()), actual.offending_text());
+        let actual = signed_numbers::<i8>("x -129").unwrap_err();
+        assert_eq!(Some("-129".to_string()), actual.offending_text());
+        assert_eq!(Ok(vec![-128i8]), signed_numbers("x -128"));
+    }
+}
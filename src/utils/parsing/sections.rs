@@ -0,0 +1,109 @@
+use crate::error::ParseError;
+use crate::utils::parsing::numbered_chunks_of_non_empty_lines;
+
+/// Block of non-empty lines whose first line is a header like "seed-to-soil map:" or "seeds: 1 2".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// Header up to its first ':' without surrounding whitespace.
+    pub name: &'a str,
+    /// Rest of the header after the ':', for example the numbers of "seeds: 1 2".
+    pub inline: &'a str,
+    /// Line number starting at 1 and text of the header.
+    pub header: (usize, &'a str),
+    /// Line numbers and text of the lines below the header.
+    pub lines: Vec<(usize, &'a str)>,
+}
+
+/// Sections of an input separated by blank lines in the order of the input, looked up by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sections<'a> {
+    sections: Vec<Section<'a>>,
+}
+
+impl<'a> Sections<'a> {
+    /// Every block needs a header with a ':' and no name may appear twice.
+    pub fn parse(text: &'a str) -> Result<Self, ParseError> {
+        let mut sections: Vec<Section<'a>> = Vec::new();
+        for chunk in numbered_chunks_of_non_empty_lines(text) {
+            let (&(line_number, header), lines) = chunk.split_first().unwrap();
+            let (name, inline) = header.split_once(':').ok_or_else(|| {
+                ParseError::new("Expected a header like \"<name>:\" as the first line of a block")
+                    .at_line(line_number)
+                    .in_text(header, 0..header.chars().count())
+            })?;
+            let name = name.trim();
+            if let Some(first) = sections.iter().find(|section| section.name == name) {
+                let start = header.find(name).unwrap_or(0);
+                let start = header[..start].chars().count();
+                return Err(ParseError::new(format!(
+                    "Duplicate section \"{}\", first given at line {}",
+                    name, first.header.0
+                ))
+                .at_line(line_number)
+                .in_text(header, start..start + name.chars().count()));
+            }
+            sections.push(Section {
+                name,
+                inline,
+                header: (line_number, header),
+                lines: lines.to_vec(),
+            });
+        }
+        Ok(Self { sections })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Section<'a>> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    /// Like [`Sections::get`] but a missing section is an error.
+    pub fn require(&self, name: &str) -> Result<&Section<'a>, ParseError> {
+        self.get(name)
+            .ok_or_else(|| ParseError::new(format!("Missing the section \"{}\"", name)))
+    }
+
+    /// Sections in the order of the input.
+    pub fn iter(&self) -> impl Iterator<Item = &Section<'a>> {
+        self.sections.iter()
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn look_up_sections_by_name() {
+        let sections = Sections::parse("seeds: 1 2\n\nb map:\n3\n4\n\n  \na map:\n5\n").unwrap();
+        let names: Vec<&str> = sections.iter().map(|section| section.name).collect();
+        assert_eq!(vec!["seeds", "b map", "a map"], names);
+        let seeds = sections.require("seeds").unwrap();
+        assert_eq!(" 1 2", seeds.inline);
+        assert!(seeds.lines.is_empty());
+        assert_eq!(
+            vec![(4, "3"), (5, "4")],
+            sections.require("b map").unwrap().lines
+        );
+        assert_eq!((8, "a map:"), sections.require("a map").unwrap().header);
+        assert_eq!(
+            "Could not parse input: Missing the section \"c map\"",
+            sections.require("c map").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn reject_invalid_sections() {
+        assert_case(
+            "a:\n1\n\nb:\n\n a :\n2",
+            "Could not parse input at line 6, column 2: Duplicate section \"a\", first given at line 1",
+        );
+        assert_case(
+            "a:\n1\n\n2\n3",
+            "Could not parse input at line 4, column 1: Expected a header like \"<name>:\" as the first line of a block",
+        );
+        fn assert_case(input: &str, expected: &str) {
+            let actual = Sections::parse(input).unwrap_err().to_string();
+            assert_eq!(expected, actual, "Input: {}", input);
+        }
+    }
+}
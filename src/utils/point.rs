@@ -0,0 +1,179 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use derive_more::Display;
+
+use crate::utils::grid_2d::Direction;
+use crate::utils::Coords;
+
+/// Signed counterpart of [`Coords`] for movement and geometry without bounds.
+/// Ordered by row first and column second like [`Coords`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Display, Copy, Hash, Default)]
+#[display(fmt = "({}, {})", y, x)]
+pub struct Point {
+    pub y: i64,
+    pub x: i64,
+}
+
+impl Point {
+    pub const ZERO: Point = Point { y: 0, x: 0 };
+
+    pub fn new(y: i64, x: i64) -> Self {
+        Self { y, x }
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> u64 {
+        self.y.abs_diff(other.y) + self.x.abs_diff(other.x)
+    }
+
+    /// Number of king moves between the points, diagonal steps included.
+    pub fn chebyshev_distance(self, other: Point) -> u64 {
+        self.y.abs_diff(other.y).max(self.x.abs_diff(other.x))
+    }
+
+    /// Smallest row and smallest column of both points, the top left corner of their bounding box.
+    pub fn component_min(self, other: Point) -> Self {
+        Self::new(self.y.min(other.y), self.x.min(other.x))
+    }
+
+    /// Biggest row and biggest column of both points, the bottom right corner of their bounding box.
+    pub fn component_max(self, other: Point) -> Self {
+        Self::new(self.y.max(other.y), self.x.max(other.x))
+    }
+
+    /// Sign of both components, a step of at most 1 towards the point from the origin.
+    pub fn signum(self) -> Self {
+        Self::new(self.y.signum(), self.x.signum())
+    }
+
+    /// Returns none if the point lies outside a grid with the given rows and columns.
+    pub fn to_coords(self, (rows, columns): (usize, usize)) -> Option<Coords> {
+        let coords = Coords {
+            y: usize::try_from(self.y).ok()?,
+            x: usize::try_from(self.x).ok()?,
+        };
+        (coords.y < rows && coords.x < columns).then_some(coords)
+    }
+}
+
+impl From<Coords> for Point {
+    /// # Panics
+    ///
+    /// If a component does not fit into an i64.
+    fn from(value: Coords) -> Self {
+        Self::new(
+            i64::try_from(value.y).unwrap(),
+            i64::try_from(value.x).unwrap(),
+        )
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((y, x): (i64, i64)) -> Self {
+        Self::new(y, x)
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(value: Direction) -> Self {
+        let (dy, dx) = value.offset();
+        Self::new(dy as i64, dx as i64)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Self::new(self.y + rhs.y, self.x + rhs.x)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Self::new(self.y - rhs.y, self.x - rhs.x)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.y, -self.x)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::new(self.y * rhs, self.x * rhs)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut point = Point::new(2, -3);
+        assert_eq!(Point::new(5, 1), point + Point::new(3, 4));
+        assert_eq!(Point::new(-1, -7), point - Point::new(3, 4));
+        assert_eq!(Point::new(-4, 6), point * -2);
+        assert_eq!(Point::new(-2, 3), -point);
+        point += Point::from(Direction::UpRight) * 3;
+        assert_eq!(Point::new(-1, 0), point);
+        point -= Point::new(1, 1);
+        assert_eq!(Point::new(-2, -1), point);
+    }
+
+    #[test]
+    fn distances() {
+        assert_case((0, 0), (0, 0), 0, 0);
+        assert_case((1, 2), (4, -2), 7, 4);
+        assert_case((-5, -5), (5, 5), 20, 10);
+        fn assert_case(left: (i64, i64), right: (i64, i64), manhattan: u64, chebyshev: u64) {
+            let (left, right) = (Point::from(left), Point::from(right));
+            let message = format!("Points: {} and {}", left, right);
+            assert_eq!(manhattan, left.manhattan_distance(right), "{}", message);
+            assert_eq!(manhattan, right.manhattan_distance(left), "{}", message);
+            assert_eq!(chebyshev, left.chebyshev_distance(right), "{}", message);
+        }
+    }
+
+    #[test]
+    fn convert_to_coords_within_grid() {
+        assert_case((0, 0), Some((0, 0)));
+        assert_case((2, 4), Some((2, 4)));
+        assert_case((3, 0), None);
+        assert_case((0, 5), None);
+        assert_case((-1, 2), None);
+        fn assert_case(point: (i64, i64), expected: Option<(usize, usize)>) {
+            let actual = Point::from(point).to_coords((3, 5));
+            assert_eq!(expected.map(Coords::from), actual, "Point: {:?}", point);
+        }
+    }
+
+    #[test]
+    fn bounding_box_corners() {
+        let (left, right) = (Point::new(3, -1), Point::new(-2, 4));
+        assert_eq!(Point::new(-2, -1), left.component_min(right));
+        assert_eq!(Point::new(3, 4), left.component_max(right));
+        assert_eq!(Point::new(1, -1), left.signum());
+        assert!(Point::new(0, 9) < Point::new(1, 0));
+    }
+}
@@ -0,0 +1,72 @@
+use std::io::IsTerminal;
+
+const HIGHLIGHT: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+/// How highlighted cells of a rendered grid are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderStyle {
+    /// No escape codes at all. Highlighted cells look like every other cell.
+    #[default]
+    Plain,
+    /// Highlighted cells are bold and red.
+    Ansi,
+}
+
+impl RenderStyle {
+    /// Colors only if stdout is a terminal so redirected output stays plain text.
+    pub fn for_stdout() -> Self {
+        if std::io::stdout().is_terminal() {
+            Self::Ansi
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+/// One line per row out of characters which are flagged as highlighted or not.
+/// Neighboring highlighted characters share one escape sequence.
+pub fn render_lines<R, C>(rows: R, style: RenderStyle) -> String
+where
+    R: Iterator<Item = C>,
+    C: Iterator<Item = (char, bool)>,
+{
+    let mut output = String::new();
+    for row in rows {
+        let mut in_highlight = false;
+        for (cell, highlighted) in row {
+            if style == RenderStyle::Ansi && highlighted != in_highlight {
+                output.push_str(if highlighted { HIGHLIGHT } else { RESET });
+                in_highlight = highlighted;
+            }
+            output.push(cell);
+        }
+        if in_highlight {
+            output.push_str(RESET);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn highlight_runs_of_cells() {
+        assert_case(RenderStyle::Plain, "abc\nde\n");
+        assert_case(
+            RenderStyle::Ansi,
+            "a\x1b[1;31mbc\x1b[0m\n\x1b[1;31md\x1b[0me\n",
+        );
+        fn assert_case(style: RenderStyle, expected: &str) {
+            let rows = [
+                vec![('a', false), ('b', true), ('c', true)],
+                vec![('d', true), ('e', false)],
+            ];
+            let actual = render_lines(rows.iter().map(|row| row.iter().copied()), style);
+            assert_eq!(expected, actual, "Style: {:?}", style);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Shortest path searches over any node type.
+//! Grids are searched by passing a closure which yields the neighbors of a cell,
+//! for example via [`crate::utils::Grid::neighbors4`] or [`crate::utils::grid_2d::go`] for an `Array2D`.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Shortest path found by a search, starting with one of the starts and ending with the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: u64,
+    pub nodes: Vec<N>,
+}
+
+impl<N> Path<N> {
+    pub fn goal(&self) -> &N {
+        self.nodes.last().unwrap()
+    }
+}
+
+/// Fewest steps from any of the starts to the first node accepted by `is_goal`.
+/// Every step costs 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut predecessors: HashMap<N, Option<N>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !predecessors.contains_key(&start) {
+            predecessors.insert(start.clone(), None);
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((node, cost)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(Path {
+                cost,
+                nodes: reconstruct(&predecessors, node),
+            });
+        }
+        for next in neighbors(&node) {
+            if !predecessors.contains_key(&next) {
+                predecessors.insert(next.clone(), Some(node.clone()));
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Fewest steps from the nearest start to every reachable node, the starts included with 0.
+pub fn bfs_distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> HashMap<N, u64>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        let cost = distances[&node];
+        for next in neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), cost + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Cheapest path from any of the starts to the first node accepted by `is_goal`.
+/// The neighbors come with the cost to step onto them.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    a_star(starts, neighbors, |_| 0, is_goal)
+}
+
+/// Like [`dijkstra`], but nodes which are estimated closer to the goal are explored first.
+/// The heuristic must never overestimate the remaining cost or the path might not be the cheapest.
+pub fn a_star<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut predecessors: HashMap<N, Option<N>> = HashMap::new();
+    let mut best_costs: HashMap<N, u64> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if !best_costs.contains_key(&start) {
+            predecessors.insert(start.clone(), None);
+            best_costs.insert(start.clone(), 0);
+            queue.push(Queued {
+                priority: heuristic(&start),
+                cost: 0,
+                node: start,
+            });
+        }
+    }
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if best_costs.get(&node).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(Path {
+                cost,
+                nodes: reconstruct(&predecessors, node),
+            });
+        }
+        for (next, step_cost) in neighbors(&node) {
+            let next_cost = cost + step_cost;
+            if best_costs.get(&next).is_some_and(|&best| best <= next_cost) {
+                continue;
+            }
+            best_costs.insert(next.clone(), next_cost);
+            predecessors.insert(next.clone(), Some(node.clone()));
+            queue.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+    None
+}
+
+/// Follows the predecessors back from the goal to the start.
+fn reconstruct<N>(predecessors: &HashMap<N, Option<N>>, goal: N) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+{
+    let mut nodes = vec![goal];
+    while let Some(Some(previous)) = predecessors.get(nodes.last().unwrap()) {
+        nodes.push(previous.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+/// Entry of the priority queue, ordered so that the lowest priority is popped first.
+struct Queued<N> {
+    priority: u64,
+    cost: u64,
+    node: N,
+}
+
+impl<N> PartialEq for Queued<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N> Eq for Queued<N> {}
+
+impl<N> PartialOrd for Queued<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Queued<N> {
+    /// Reversed because the heap of the standard library pops the biggest entry.
+    /// On equal priority the entry which got further already wins.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::utils::{grid_2d, Coords, Grid, Point};
+
+    const MAZE: &str = "S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#G";
+
+    fn maze() -> Grid<char> {
+        Grid::from(grid_2d::text_to_grid(MAZE, |cell| cell))
+    }
+
+    fn find(grid: &Grid<char>, wanted: char) -> Coords {
+        grid.iter()
+            .find(|(_, cell)| **cell == wanted)
+            .map(|(coords, _)| coords)
+            .unwrap()
+    }
+
+    fn open_neighbors(grid: &Grid<char>, coords: Coords) -> Vec<Coords> {
+        grid.neighbors4(coords)
+            .filter(|(_, cell)| **cell != '#')
+            .map(|(coords, _)| coords)
+            .collect()
+    }
+
+    #[test]
+    fn bfs_through_maze() {
+        let grid = maze();
+        let (start, goal) = (find(&grid, 'S'), find(&grid, 'G'));
+        let path = bfs(
+            [start],
+            |&coords| open_neighbors(&grid, coords),
+            |&coords| coords == goal,
+        )
+        .unwrap();
+        assert_eq!(15, path.cost);
+        assert_eq!(16, path.nodes.len());
+        assert_eq!(start, path.nodes[0]);
+        assert_eq!(&goal, path.goal());
+        for step in path.nodes.windows(2) {
+            assert_eq!(
+                1,
+                Point::from(step[0]).manhattan_distance(Point::from(step[1]))
+            );
+        }
+
+        let distances = bfs_distances([start], |&coords| open_neighbors(&grid, coords));
+        assert_eq!(Some(&15), distances.get(&goal));
+        assert_eq!(None, distances.get(&Coords::new(0, 3)));
+    }
+
+    #[test]
+    fn bfs_from_several_starts() {
+        let grid = maze();
+        let goal = find(&grid, 'G');
+        let starts = [find(&grid, 'S'), Coords::new(0, 7)];
+        let path = bfs(
+            starts,
+            |&coords| open_neighbors(&grid, coords),
+            |&coords| coords == goal,
+        )
+        .unwrap();
+        assert_eq!(Coords::new(0, 7), path.nodes[0]);
+        assert_eq!(4, path.cost);
+    }
+
+    #[test]
+    fn weighted_search_on_grid() {
+        let grid = Grid::from(grid_2d::text_to_grid("1163\n1381\n2136", |cell| {
+            cell.to_digit(10).unwrap() as u64
+        }));
+        let goal = Coords::new(2, 3);
+        let neighbors = |coords: &Coords| -> Vec<(Coords, u64)> {
+            grid.neighbors4(*coords)
+                .map(|(coords, cost)| (coords, *cost))
+                .collect()
+        };
+        let expected = dijkstra([Coords::new(0, 0)], neighbors, |&coords| coords == goal).unwrap();
+        assert_eq!(13, expected.cost);
+        let actual = a_star(
+            [Coords::new(0, 0)],
+            neighbors,
+            |&coords| Point::from(coords).manhattan_distance(Point::from(goal)),
+            |&coords| coords == goal,
+        )
+        .unwrap();
+        assert_eq!(expected.cost, actual.cost);
+    }
+
+    #[test]
+    fn search_over_named_nodes() {
+        let edges: HashMap<&str, Vec<(&str, u64)>> = HashMap::from([
+            ("home", vec![("park", 4), ("shop", 1)]),
+            ("shop", vec![("park", 2), ("school", 7)]),
+            ("park", vec![("school", 3)]),
+        ]);
+        let neighbors = |node: &&str| edges.get(node).cloned().unwrap_or_default();
+        let path = dijkstra(["home"], neighbors, |node| *node == "school").unwrap();
+        assert_eq!(6, path.cost);
+        assert_eq!(vec!["home", "shop", "park", "school"], path.nodes);
+        assert_eq!(
+            None,
+            dijkstra(["school"], neighbors, |node| *node == "home")
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::utils::grid_2d::Direction;
+use crate::utils::render::{self, RenderStyle};
+use crate::utils::{Coords, Grid, Point};
+
+/// Unbounded grid which only stores the cells set so far.
+/// Every other cell has the default value given on creation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+    /// Top left and bottom right corner of all stored cells, both inclusive.
+    /// Also covers the whole extent of a dense grid the cells came from.
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    /// Stores every cell of the dense grid with row 0 and column 0 at the origin.
+    /// Cells with the default value are skipped.
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Self
+    where
+        T: Clone + PartialEq,
+    {
+        Self::from_grid_at(grid, Point::ZERO, default)
+    }
+
+    /// Stores every cell of the dense grid with its top left corner at `origin`.
+    /// Cells with the default value are skipped but the bounding box still spans the whole
+    /// dense grid, so converting it back gives an equal grid even with default cells at the border.
+    pub fn from_grid_at(grid: &Grid<T>, origin: Point, default: T) -> Self
+    where
+        T: Clone + PartialEq,
+    {
+        let mut sparse = Self::new(default);
+        for (coords, cell) in grid.iter() {
+            if *cell != sparse.default {
+                sparse.insert(origin + Point::from(coords), cell.clone());
+            }
+        }
+        if grid.num_rows() > 0 && grid.num_columns() > 0 {
+            let bottom_right = Coords::new(grid.num_rows() - 1, grid.num_columns() - 1);
+            sparse.extend_bounds(origin);
+            sparse.extend_bounds(origin + Point::from(bottom_right));
+        }
+        sparse
+    }
+
+    /// Dense copy of the bounding box together with the point of its top left corner.
+    /// Cells which were never set get the default value.
+    pub fn to_grid(&self) -> (Point, Grid<T>)
+    where
+        T: Clone,
+    {
+        let Some((top_left, bottom_right)) = self.bounds else {
+            return (Point::ZERO, Grid::filled_with(self.default.clone(), 0, 0));
+        };
+        let size = bottom_right - top_left + Point::new(1, 1);
+        let mut grid = Grid::filled_with(self.default.clone(), size.y as usize, size.x as usize);
+        for (point, cell) in self.iter() {
+            let coords = (point - top_left)
+                .to_coords((grid.num_rows(), grid.num_columns()))
+                .unwrap();
+            grid[coords] = cell.clone();
+        }
+        (top_left, grid)
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// Returns the default value for cells which were never set.
+    pub fn get(&self, point: Point) -> &T {
+        self.cells.get(&point).unwrap_or(&self.default)
+    }
+
+    /// Stores the default value first if the cell was never set.
+    pub fn get_mut(&mut self, point: Point) -> &mut T
+    where
+        T: Clone,
+    {
+        self.extend_bounds(point);
+        self.cells
+            .entry(point)
+            .or_insert_with(|| self.default.clone())
+    }
+
+    /// Returns the value stored before, if any.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.extend_bounds(point);
+        self.cells.insert(point, value)
+    }
+
+    /// Returns the removed value, if any. The bounding box shrinks to the remaining cells.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+        if let Some((top_left, bottom_right)) = self.bounds {
+            let on_border = point.y == top_left.y
+                || point.y == bottom_right.y
+                || point.x == top_left.x
+                || point.x == bottom_right.x;
+            if on_border {
+                self.bounds = self
+                    .cells
+                    .keys()
+                    .fold(None, |bounds, &point| Some(extended(bounds, point)));
+            }
+        }
+        Some(removed)
+    }
+
+    /// Number of stored cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Top left and bottom right corner of all stored cells, both inclusive.
+    /// None as long as no cell is stored and the grid did not come from a dense one.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// Stored cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, cell)| (point, cell))
+    }
+
+    /// Always the 4 orthogonal neighbors in reading order, default values included.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors_by(point, &Direction::ORTHOGONAL)
+    }
+
+    /// Always all 8 neighbors in reading order, default values included.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors_by(point, &Direction::ALL)
+    }
+
+    /// One line per row of the bounding box with every cell mapped to a character.
+    pub fn render(&self, to_char: impl Fn(&T) -> char) -> String {
+        self.render_highlighted(to_char, &HashSet::new(), RenderStyle::Plain)
+    }
+
+    /// Like [`SparseGrid::render`] with the given cells highlighted according to the style.
+    pub fn render_highlighted(
+        &self,
+        to_char: impl Fn(&T) -> char,
+        highlighted: &HashSet<Point>,
+        style: RenderStyle,
+    ) -> String {
+        let Some((top_left, bottom_right)) = self.bounds else {
+            return String::new();
+        };
+        let to_char = &to_char;
+        let rows = (top_left.y..=bottom_right.y).map(|y| {
+            (top_left.x..=bottom_right.x).map(move |x| {
+                let point = Point::new(y, x);
+                (to_char(self.get(point)), highlighted.contains(&point))
+            })
+        });
+        render::render_lines(rows, style)
+    }
+
+    fn neighbors_by<'a>(
+        &'a self,
+        point: Point,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Point, &'a T)> {
+        directions.iter().map(move |&direction| {
+            let moved = point + Point::from(direction);
+            (moved, self.get(moved))
+        })
+    }
+
+    fn extend_bounds(&mut self, point: Point) {
+        self.bounds = Some(extended(self.bounds, point));
+    }
+}
+
+fn extended(bounds: Option<(Point, Point)>, point: Point) -> (Point, Point) {
+    match bounds {
+        None => (point, point),
+        Some((top_left, bottom_right)) => (
+            top_left.component_min(point),
+            bottom_right.component_max(point),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn track_bounding_box() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(None, grid.bounding_box());
+        grid.insert(Point::new(2, -3), '#');
+        grid.insert(Point::new(-1, 4), '#');
+        grid.insert(Point::new(0, 0), '#');
+        assert_eq!(
+            Some((Point::new(-1, -3), Point::new(2, 4))),
+            grid.bounding_box()
+        );
+        grid.remove(Point::new(2, -3));
+        assert_eq!(
+            Some((Point::new(-1, 0), Point::new(0, 4))),
+            grid.bounding_box()
+        );
+        grid.remove(Point::new(-1, 4));
+        grid.remove(Point::new(0, 0));
+        assert_eq!(None, grid.bounding_box());
+    }
+
+    #[test]
+    fn neighbors_with_default_values() {
+        let mut grid = SparseGrid::new(0);
+        grid.insert(Point::new(-1, 0), 1);
+        *grid.get_mut(Point::new(0, 1)) += 2;
+        let actual: Vec<(Point, i32)> = grid
+            .neighbors4(Point::ZERO)
+            .map(|(point, value)| (point, *value))
+            .collect();
+        assert_eq!(
+            vec![
+                (Point::new(-1, 0), 1),
+                (Point::new(0, -1), 0),
+                (Point::new(0, 1), 2),
+                (Point::new(1, 0), 0),
+            ],
+            actual
+        );
+        assert_eq!(8, grid.neighbors8(Point::new(100, -100)).count());
+    }
+
+    #[test]
+    fn render_bounding_box() {
+        let mut grid = SparseGrid::new('.');
+        grid.insert(Point::new(-1, -1), '#');
+        grid.insert(Point::new(1, 2), '#');
+        assert_eq!("#...\n....\n...#\n", grid.render(|cell| *cell));
+        assert_eq!("", SparseGrid::new('.').render(|cell| *cell));
+        let highlighted = HashSet::from([Point::new(-1, -1), Point::new(0, 0)]);
+        assert_eq!(
+            "\x1b[1;31m#\x1b[0m...\n.\x1b[1;31m.\x1b[0m..\n...#\n",
+            grid.render_highlighted(|cell| *cell, &highlighted, RenderStyle::Ansi)
+        );
+    }
+
+    #[test]
+    fn convert_between_dense_and_sparse_losslessly() {
+        let dense = Grid::from_rows(vec![vec!['a', '.', 'c'], vec!['.', 'e', '.']]).unwrap();
+        let sparse = SparseGrid::from_grid(&dense, '.');
+        assert_eq!(3, sparse.len());
+        assert_eq!(dense.render(|cell| *cell), sparse.render(|cell| *cell));
+        assert_eq!((Point::ZERO, dense.clone()), sparse.to_grid());
+
+        let mut sparse = SparseGrid::new('.');
+        sparse.insert(Point::new(-2, 5), 'x');
+        sparse.insert(Point::new(0, 3), 'y');
+        let (origin, dense) = sparse.to_grid();
+        assert_eq!(Point::new(-2, 3), origin);
+        let back = SparseGrid::from_grid_at(&dense, origin, '.');
+        assert_eq!(sparse, back);
+    }
+
+    #[test]
+    fn keep_default_cells_at_the_border_of_dense_grids() {
+        let dense = Grid::from_rows(vec![vec!['.', 'a', '.'], vec!['.', '.', '.']]).unwrap();
+        let sparse = SparseGrid::from_grid_at(&dense, Point::new(3, -1), '.');
+        assert_eq!(1, sparse.len());
+        assert_eq!(
+            Some((Point::new(3, -1), Point::new(4, 1))),
+            sparse.bounding_box()
+        );
+        assert_eq!((Point::new(3, -1), dense), sparse.to_grid());
+
+        let only_defaults = Grid::filled_with('.', 2, 2);
+        let sparse = SparseGrid::from_grid(&only_defaults, '.');
+        assert!(sparse.is_empty());
+        assert_eq!((Point::ZERO, only_defaults), sparse.to_grid());
+    }
+}